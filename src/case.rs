@@ -0,0 +1,162 @@
+// Converts table and column names into the case each format expects. Names
+// are broken into words first, so empty segments like the ones in `_audit`
+// or `legacy__rows` are skipped rather than tripped over.
+
+use serde::Deserialize;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Case {
+  #[default]
+  Snake,
+  Camel,
+  Pascal,
+  Kebab,
+  ScreamingSnake,
+}
+
+// Splits a name on separators and at the start of each capitalised word, so
+// `created_at`, `createdAt` and `CreatedAt` all become ["created", "at"]. A
+// run of capitals stays together, like the `HTML` in `HTMLParser`.
+pub fn words(name: &str) -> Vec<String> {
+  let characters = name.chars().collect::<Vec<char>>();
+  let mut words = vec![];
+  let mut word = String::new();
+  for (index, &character) in characters.iter().enumerate() {
+    if !character.is_alphanumeric() {
+      if !word.is_empty() {
+        words.push(std::mem::take(&mut word));
+      }
+      continue;
+    }
+    let previous = index.checked_sub(1).map(|index| characters[index]);
+    let next = characters.get(index + 1);
+    let starts_word = character.is_uppercase()
+      && match previous {
+        Some(previous) if previous.is_lowercase() || previous.is_ascii_digit() => true,
+        Some(previous) if previous.is_uppercase() => next.is_some_and(|next| next.is_lowercase()),
+        _ => false,
+      };
+    if starts_word && !word.is_empty() {
+      words.push(std::mem::take(&mut word));
+    }
+    word.push(character);
+  }
+  if !word.is_empty() {
+    words.push(word);
+  }
+  words
+}
+
+// Whether a name can be written as is in Rust, TypeScript, Go and Clojure:
+// letters, digits and underscores, and not starting with a digit
+pub fn is_identifier(name: &str) -> bool {
+  let mut characters = name.chars();
+  characters
+    .next()
+    .is_some_and(|first| first.is_alphabetic() || first == '_')
+    && characters.all(|character| character.is_alphanumeric() || character == '_')
+}
+
+// Upper cases the first letter of a word and lower cases the rest, unless
+// it's one of the acronyms, which are written the way they're listed
+fn capitalize(word: &str, acronyms: &[String]) -> String {
+  if let Some(acronym) = acronyms
+    .iter()
+    .find(|acronym| acronym.eq_ignore_ascii_case(word))
+  {
+    return acronym.clone();
+  }
+  let mut characters = word.chars();
+  match characters.next() {
+    Some(first) => first
+      .to_uppercase()
+      .chain(characters.flat_map(char::to_lowercase))
+      .collect(),
+    None => String::new(),
+  }
+}
+
+impl Case {
+  pub fn convert(&self, name: &str, acronyms: &[String]) -> String {
+    let words = words(name);
+    let join = |separator: &str, word: fn(&String) -> String| {
+      words
+        .iter()
+        .map(word)
+        .collect::<Vec<String>>()
+        .join(separator)
+    };
+    match self {
+      Case::Snake => join("_", |word| word.to_lowercase()),
+      Case::Kebab => join("-", |word| word.to_lowercase()),
+      Case::ScreamingSnake => join("_", |word| word.to_uppercase()),
+      Case::Pascal => words
+        .iter()
+        .map(|word| capitalize(word, acronyms))
+        .collect(),
+      Case::Camel => words
+        .iter()
+        .enumerate()
+        .map(|(index, word)| match index {
+          0 => word.to_lowercase(),
+          _ => capitalize(word, acronyms),
+        })
+        .collect(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{is_identifier, words, Case};
+
+  #[test]
+  fn it_splits_names_into_words() {
+    assert_eq!(words("created_at"), vec!["created", "at"]);
+    assert_eq!(words("createdAt"), vec!["created", "At"]);
+    assert_eq!(words("HTMLParser"), vec!["HTML", "Parser"]);
+    assert_eq!(words("address_line2"), vec!["address", "line2"]);
+    assert_eq!(words("line2Total"), vec!["line2", "Total"]);
+    assert_eq!(words("kebab-case name"), vec!["kebab", "case", "name"]);
+  }
+
+  #[test]
+  fn it_does_not_panic_on_empty_segments() {
+    assert_eq!(Case::Pascal.convert("_audit", &[]), "Audit");
+    assert_eq!(Case::Pascal.convert("legacy__rows", &[]), "LegacyRows");
+    assert_eq!(Case::Snake.convert("legacy__rows_", &[]), "legacy_rows");
+    assert_eq!(Case::Pascal.convert("__", &[]), "");
+  }
+
+  #[test]
+  fn it_converts_between_cases() {
+    let name = "user_profile_url";
+    assert_eq!(Case::Snake.convert(name, &[]), "user_profile_url");
+    assert_eq!(Case::Camel.convert(name, &[]), "userProfileUrl");
+    assert_eq!(Case::Pascal.convert(name, &[]), "UserProfileUrl");
+    assert_eq!(Case::Kebab.convert(name, &[]), "user-profile-url");
+    assert_eq!(Case::ScreamingSnake.convert(name, &[]), "USER_PROFILE_URL");
+    assert_eq!(
+      Case::Snake.convert("userProfileURL", &[]),
+      "user_profile_url"
+    );
+  }
+
+  #[test]
+  fn identifiers_cannot_start_with_a_digit() {
+    assert!(is_identifier("_audit"));
+    assert!(is_identifier("line2"));
+    assert!(!is_identifier("123abc"));
+    assert!(!is_identifier("kebab-case"));
+    assert!(!is_identifier(""));
+  }
+
+  #[test]
+  fn acronyms_keep_their_capitals() {
+    let acronyms = vec!["ID".to_string(), "URL".to_string()];
+    assert_eq!(Case::Pascal.convert("user_id", &acronyms), "UserID");
+    assert_eq!(Case::Camel.convert("avatar_url", &acronyms), "avatarURL");
+    assert_eq!(Case::Camel.convert("id", &acronyms), "id");
+  }
+}
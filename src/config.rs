@@ -0,0 +1,143 @@
+use std::{fmt, fs, path::Path, str::FromStr};
+
+use serde::Deserialize;
+
+use super::{
+  diesel::DieselConfig, drizzle::DrizzleConfig, go::GoConfig, inflector::Inflections,
+  relationships::RelationshipConfig, rust::RustConfig, spec::SpecConfig,
+  typescript::TypeScriptConfig, Variant,
+};
+
+#[derive(Debug)]
+pub struct ConfigError(String);
+
+impl fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl std::error::Error for ConfigError {}
+
+// The database the schema was dumped from. Rails leaves the width of
+// some columns up to the adapter, so we need to know which one to ask.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Adapter {
+  #[default]
+  Postgresql,
+  Mysql,
+  Sqlite,
+}
+
+// Whether a table's type is named for one of its rows, like Rails models,
+// or for the table itself
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TypeNames {
+  #[default]
+  Singular,
+  Plural,
+}
+
+// Naming for the insert and patch types generated alongside each table,
+// where `{}` is replaced with the table's type name
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct VariantConfig {
+  pub enabled: bool,
+  pub insert: String,
+  pub patch: String,
+}
+
+impl Default for VariantConfig {
+  fn default() -> Self {
+    VariantConfig {
+      enabled: false,
+      insert: "New{}".to_string(),
+      patch: "{}Patch".to_string(),
+    }
+  }
+}
+
+impl VariantConfig {
+  pub fn name(&self, variant: Variant, name: &str) -> String {
+    match variant {
+      Variant::Insert => self.insert.replace("{}", name),
+      Variant::Patch => self.patch.replace("{}", name),
+    }
+  }
+}
+
+// Project wide settings, normally read from a turbine.toml file. Every
+// key is optional and falls back to its default.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+  pub adapter: Adapter,
+  pub type_names: TypeNames,
+  // Words written in capitals wherever a name is PascalCased or camelCased, e.g. ["ID", "URL"]
+  pub acronyms: Vec<String>,
+  pub inflections: Inflections,
+  pub relationships: RelationshipConfig,
+  pub rust: RustConfig,
+  pub typescript: TypeScriptConfig,
+  pub go: GoConfig,
+  pub spec: SpecConfig,
+  pub diesel: DieselConfig,
+  pub drizzle: DrizzleConfig,
+}
+
+impl FromStr for Config {
+  type Err = ConfigError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    toml::from_str(s).map_err(|why| ConfigError(why.to_string()))
+  }
+}
+
+impl Config {
+  pub fn from_path(path: &Path) -> Result<Self, ConfigError> {
+    let contents = fs::read_to_string(path)
+      .map_err(|why| ConfigError(format!("couldn't read {}: {}", path.display(), why)))?;
+    contents.parse()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Adapter, Config};
+  use crate::{go::GoDecimal, rust::RustDecimal, typescript::TypeScriptDecimal};
+
+  #[test]
+  fn an_empty_config_is_the_default_config() {
+    let config: Config = "".parse().unwrap();
+    assert_eq!(config.adapter, Adapter::Postgresql);
+    assert_eq!(config.rust.decimal, RustDecimal::RustDecimal);
+  }
+
+  #[test]
+  fn it_reads_settings_for_each_generator() {
+    let config: Config = "adapter = \"mysql\"
+
+[rust]
+decimal = \"big-decimal\"
+
+[typescript]
+decimal = \"big\"
+
+[go]
+decimal = \"string\""
+      .parse()
+      .unwrap();
+    assert_eq!(config.adapter, Adapter::Mysql);
+    assert_eq!(config.rust.decimal, RustDecimal::BigDecimal);
+    assert_eq!(config.typescript.decimal, TypeScriptDecimal::Big);
+    assert_eq!(config.go.decimal, GoDecimal::String);
+  }
+
+  #[test]
+  fn it_rejects_keys_it_does_not_know_about() {
+    assert!("adaptor = \"mysql\"".parse::<Config>().is_err())
+  }
+}
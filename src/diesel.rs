@@ -0,0 +1,727 @@
+use serde::Deserialize;
+
+use super::case::{is_identifier, Case};
+use super::config::{Adapter, Config, VariantConfig};
+use super::rust::{
+  declaration_with, enum_declaration, enum_name, enum_variants, escape, field, field_name,
+  type_name, visibility, with_imports, RustDateTime, ToRust,
+};
+use super::ColumnData;
+use super::Database;
+use super::EnumType;
+use super::NumericType;
+use super::RailsColumn;
+use super::Table;
+use super::Variant;
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DieselConfig {
+  // Emit a Queryable struct for each table after the schema
+  pub models: bool,
+  // Naming for the Insertable and AsChangeset structs that go with each model
+  pub variants: VariantConfig,
+}
+
+impl Default for DieselConfig {
+  fn default() -> Self {
+    DieselConfig {
+      models: true,
+      variants: VariantConfig {
+        enabled: true,
+        insert: "New{}".to_string(),
+        patch: "{}Changeset".to_string(),
+      },
+    }
+  }
+}
+
+pub trait ToDiesel {
+  fn to_diesel(&self, config: &Config) -> String;
+}
+
+impl ToDiesel for Database {
+  fn to_diesel(&self, config: &Config) -> String {
+    let mut items = vec![];
+    let custom_types = custom_types(self);
+    if !custom_types.is_empty() {
+      items.push(sql_types_module(&custom_types, config));
+    }
+    items.extend(
+      self
+        .enums()
+        .into_iter()
+        .filter(|enum_type| !enum_type.values.is_empty())
+        .map(|enum_type| rust_enum(enum_type, config)),
+    );
+
+    // Diesel can't describe a table without a primary key
+    let (tables, skipped): (Vec<&Table>, Vec<&Table>) = self
+      .0
+      .iter()
+      .partition(|table| table.columns.iter().any(ColumnData::is_primary_key));
+    items.extend(skipped.iter().map(|table| {
+      format!(
+        "// {} has no primary key, so it can't be used with diesel::table!",
+        table.name
+      )
+    }));
+    items.extend(tables.iter().map(|table| table_macro(table, config)));
+
+    let joins = joinables(&tables);
+    if !joins.is_empty() {
+      items.push(joins.join("\n"));
+    }
+    if tables.len() > 1 {
+      let names = tables
+        .iter()
+        .map(|table| format!("    {},", ident(&table.name)))
+        .collect::<Vec<String>>()
+        .join("\n");
+      items.push(format!(
+        "diesel::allow_tables_to_appear_in_same_query!(\n{}\n);",
+        names
+      ));
+    }
+
+    if config.diesel.models {
+      items.extend(tables.iter().map(|table| models(table, config)));
+    }
+    let type_names = tables
+      .iter()
+      .map(|table| type_name(table, config))
+      .chain(
+        self
+          .enums()
+          .iter()
+          .map(|enum_type| enum_name(&enum_type.name)),
+      )
+      .collect::<Vec<String>>();
+    with_imports(&items.join("\n\n"), &type_names)
+  }
+}
+
+// Table and column names as they're written in the schema, escaped the way
+// the Rust format escapes them. Diesel can't take raw identifiers, so
+// keywords get a trailing underscore instead.
+fn ident(name: &str) -> String {
+  let name = if is_identifier(name) {
+    name.to_string()
+  } else {
+    Case::Snake.convert(name, &[])
+  };
+  let name = escape(name);
+  match name.strip_prefix("r#") {
+    Some(keyword) => format!("{}_", keyword),
+    None => name,
+  }
+}
+
+// An `#[sql_name]` for anything whose identifier isn't its real name
+fn sql_name(name: &str, indent: &str) -> String {
+  if ident(name) == name {
+    String::new()
+  } else {
+    format!("{}#[sql_name = {:?}]\n", indent, name)
+  }
+}
+
+// Columns whose type Diesel doesn't know, named the way print-schema names
+// them. An enum without any known values is read as text instead.
+fn custom_type(column: &ColumnData) -> Option<(String, String)> {
+  let name = match (&column.value_type, &column.options.enum_type) {
+    (RailsColumn::HStore, _) => "hstore".to_string(),
+    (RailsColumn::Enum, Some(enum_type)) if !enum_type.values.is_empty() => enum_type.name.clone(),
+    _ => return None,
+  };
+  Some((enum_name(&name), name))
+}
+
+fn custom_types(database: &Database) -> Vec<(String, String)> {
+  let mut custom_types: Vec<(String, String)> = vec![];
+  let columns = database.0.iter().flat_map(|table| table.columns.iter());
+  for custom_type in columns.filter_map(custom_type) {
+    if !custom_types.contains(&custom_type) {
+      custom_types.push(custom_type);
+    }
+  }
+  custom_types
+}
+
+fn sql_types_module(custom_types: &[(String, String)], config: &Config) -> String {
+  let types = custom_types
+    .iter()
+    .map(|(type_name, name)| {
+      format!(
+        "    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]\n    #[diesel(postgres_type(name = {:?}))]\n    pub struct {};",
+        name, type_name
+      )
+    })
+    .collect::<Vec<String>>()
+    .join("\n\n");
+  format!("{}mod sql_types {{\n{}\n}}", visibility(config), types)
+}
+
+// A Rust enum for a Postgres enum, which Diesel reads and writes as its label
+fn rust_enum(enum_type: &EnumType, config: &Config) -> String {
+  let name = enum_name(&enum_type.name);
+  let variants = enum_variants(enum_type, config);
+  let to_sql = variants
+    .iter()
+    .map(|(variant, value)| format!("            {}::{} => {:?},", name, variant, value))
+    .collect::<Vec<String>>()
+    .join("\n");
+  let from_sql = variants
+    .iter()
+    .map(|(variant, value)| format!("            {:?} => Ok({}::{}),", value, name, variant))
+    .collect::<Vec<String>>()
+    .join("\n");
+  [
+    enum_declaration(
+      enum_type,
+      &["diesel::AsExpression", "diesel::FromSqlRow"],
+      &[format!("#[diesel(sql_type = sql_types::{})]", name)],
+      &[("serde", "rename")],
+      config,
+    ),
+    format!(
+      "impl diesel::serialize::ToSql<sql_types::{}, diesel::pg::Pg> for {} {{\n    fn to_sql<'b>(&'b self, out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>) -> diesel::serialize::Result {{\n        let label = match self {{\n{}\n        }};\n        std::io::Write::write_all(out, label.as_bytes())?;\n        Ok(diesel::serialize::IsNull::No)\n    }}\n}}",
+      name, name, to_sql
+    ),
+    format!(
+      "impl diesel::deserialize::FromSql<sql_types::{}, diesel::pg::Pg> for {} {{\n    fn from_sql(value: diesel::pg::PgValue<'_>) -> diesel::deserialize::Result<Self> {{\n        match std::str::from_utf8(value.as_bytes())? {{\n{}\n            label => Err(format!(\"unrecognized {}: {{}}\", label).into()),\n        }}\n    }}\n}}",
+      name, name, from_sql, enum_type.name
+    ),
+  ]
+  .join("\n\n")
+}
+
+fn sql_type(column: &ColumnData, config: &Config) -> String {
+  let sql_type = match (&column.value_type, column.numeric_type(config.adapter)) {
+    // A decimal is still stored as one, whatever its precision
+    (RailsColumn::Decimal | RailsColumn::Numeric, _) => "Numeric".to_string(),
+    (_, Some(NumericType::SmallInt)) => "SmallInt".to_string(),
+    (_, Some(NumericType::Int)) => "Integer".to_string(),
+    (_, Some(NumericType::BigInt)) => "BigInt".to_string(),
+    (_, Some(NumericType::Float)) => "Double".to_string(),
+    (_, Some(NumericType::Decimal)) => "Numeric".to_string(),
+    (RailsColumn::String, _) => "Varchar".to_string(),
+    (RailsColumn::Text, _) => "Text".to_string(),
+    (RailsColumn::Datetime, _) => "Timestamp".to_string(),
+    (RailsColumn::Time, _) => "Time".to_string(),
+    (RailsColumn::Date, _) => "Date".to_string(),
+    (RailsColumn::Binary, _) => "Binary".to_string(),
+    (RailsColumn::Boolean, _) => "Bool".to_string(),
+    (RailsColumn::JsonB, _) if config.adapter == Adapter::Postgresql => "Jsonb".to_string(),
+    (RailsColumn::JsonB, _) => "Json".to_string(),
+    (RailsColumn::Uuid, _) => "Uuid".to_string(),
+    (RailsColumn::HStore | RailsColumn::Enum, _) => {
+      custom_type(column).map_or_else(|| "Text".to_string(), |(type_name, _)| type_name)
+    }
+    (RailsColumn::PrimaryKey | RailsColumn::Integer | RailsColumn::Bigint, None)
+    | (RailsColumn::Float, None) => unreachable!("numeric columns have a numeric type"),
+  };
+  let sql_type = if column.options.array {
+    format!("Array<{}>", sql_type)
+  } else {
+    sql_type
+  };
+  if column.nullable {
+    format!("Nullable<{}>", sql_type)
+  } else {
+    sql_type
+  }
+}
+
+fn primary_keys(table: &Table) -> Vec<&ColumnData> {
+  table
+    .columns
+    .iter()
+    .filter(|column| column.is_primary_key())
+    .collect()
+}
+
+fn table_macro(table: &Table, config: &Config) -> String {
+  let mut uses = vec!["    use diesel::sql_types::*;".to_string()];
+  let mut custom_types = table
+    .columns
+    .iter()
+    .filter_map(custom_type)
+    .map(|(type_name, _)| type_name)
+    .collect::<Vec<String>>();
+  custom_types.sort();
+  custom_types.dedup();
+  uses.extend(
+    custom_types
+      .iter()
+      .map(|type_name| format!("    use super::sql_types::{};", type_name)),
+  );
+
+  let keys = primary_keys(table)
+    .iter()
+    .map(|column| ident(&column.name))
+    .collect::<Vec<String>>()
+    .join(", ");
+  let columns = table
+    .columns
+    .iter()
+    .map(|column| {
+      format!(
+        "{}        {} -> {},",
+        sql_name(&column.name, "        "),
+        ident(&column.name),
+        sql_type(column, config)
+      )
+    })
+    .collect::<Vec<String>>()
+    .join("\n");
+  format!(
+    "diesel::table! {{\n{}\n\n{}    {} ({}) {{\n{}\n    }}\n}}",
+    uses.join("\n"),
+    sql_name(&table.name, "    "),
+    ident(&table.name),
+    keys,
+    columns
+  )
+}
+
+// One `joinable!` for each pair of tables, as Diesel only allows one. The
+// foreign key has to point at the other table's primary key.
+fn joinables(tables: &[&Table]) -> Vec<String> {
+  let mut pairs: Vec<(&str, &str)> = vec![];
+  let mut joins = vec![];
+  for table in tables {
+    for column in &table.columns {
+      let Some(foreign_key) = &column.options.foreign_key else {
+        continue;
+      };
+      let parent = tables
+        .iter()
+        .find(|parent| parent.name == foreign_key.table);
+      let references_key = parent.is_some_and(|parent| {
+        primary_keys(parent)
+          .iter()
+          .any(|key| key.name == foreign_key.primary_key)
+      });
+      let pair = (table.name.as_str(), foreign_key.table.as_str());
+      if !references_key || pair.0 == pair.1 || pairs.contains(&pair) {
+        continue;
+      }
+      pairs.push(pair);
+      joins.push(format!(
+        "diesel::joinable!({} -> {} ({}));",
+        ident(&table.name),
+        ident(&foreign_key.table),
+        ident(&column.name)
+      ));
+    }
+  }
+  joins
+}
+
+// The Rust type Diesel loads the column's SQL type into. Rails timestamps
+// don't have a time zone, and decimals are always numeric.
+fn value_type(column: &ColumnData, config: &Config) -> String {
+  let chrono = config.rust.date_time != RustDateTime::Time;
+  let value_type = match &column.value_type {
+    RailsColumn::Datetime if chrono => "chrono::NaiveDateTime".to_string(),
+    RailsColumn::Date if chrono => "chrono::NaiveDate".to_string(),
+    RailsColumn::Time if chrono => "chrono::NaiveTime".to_string(),
+    RailsColumn::Datetime => "time::PrimitiveDateTime".to_string(),
+    RailsColumn::Date => "time::Date".to_string(),
+    RailsColumn::Time => "time::Time".to_string(),
+    RailsColumn::Decimal | RailsColumn::Numeric => NumericType::Decimal.to_rust(config),
+    RailsColumn::Enum => match &column.options.enum_type {
+      Some(enum_type) if !enum_type.values.is_empty() => enum_name(&enum_type.name),
+      _ => "String".to_string(),
+    },
+    value_type => match column.numeric_type(config.adapter) {
+      Some(numeric_type) => numeric_type.to_rust(config),
+      None => value_type.to_rust(config),
+    },
+  };
+  if column.options.array {
+    format!("Vec<{}>", value_type)
+  } else {
+    value_type
+  }
+}
+
+// A model field, pointed back at its column when the field has another name
+fn model_field(column: &ColumnData, value_type: &str, config: &Config) -> String {
+  let field = field(&column.name, value_type, config);
+  let column_name = ident(&column.name);
+  if field_name(&column.name, config) == column_name {
+    format!("   {}", field)
+  } else {
+    format!("   #[diesel(column_name = {})]\n   {}", column_name, field)
+  }
+}
+
+fn model(
+  table: &Table,
+  name: &str,
+  fields: Vec<String>,
+  derives: &[&str],
+  attributes: &[String],
+  config: &Config,
+) -> String {
+  let fields = fields
+    .into_iter()
+    .fold(String::new(), |fields, field| [fields, field].join("\n"));
+  let mut attributes = attributes.to_vec();
+  attributes.insert(0, format!("#[diesel(table_name = {})]", ident(&table.name)));
+  declaration_with(name, &fields, derives, &attributes, config)
+}
+
+// The Queryable struct, along with the Insertable and AsChangeset structs
+// for writing to the table
+fn models(table: &Table, config: &Config) -> String {
+  let name = type_name(table, config);
+  let fields = table
+    .columns
+    .iter()
+    .map(|column| {
+      let value_type = value_type(column, config);
+      if column.nullable {
+        model_field(column, &format!("Option<{}>", value_type), config)
+      } else {
+        model_field(column, &value_type, config)
+      }
+    })
+    .collect();
+  let keys = primary_keys(table)
+    .iter()
+    .map(|column| field_name(&column.name, config))
+    .collect::<Vec<String>>();
+  let attributes = match keys.as_slice() {
+    [key] if key == "id" => vec![],
+    keys => vec![format!("#[diesel(primary_key({}))]", keys.join(", "))],
+  };
+  let mut items = vec![model(
+    table,
+    &name,
+    fields,
+    &[
+      "diesel::Queryable",
+      "diesel::Selectable",
+      "diesel::Identifiable",
+    ],
+    &attributes,
+    config,
+  )];
+
+  let variants = &config.diesel.variants;
+  if variants.enabled {
+    for (variant, derive) in [
+      (Variant::Insert, "diesel::Insertable"),
+      (Variant::Patch, "diesel::AsChangeset"),
+    ] {
+      let fields = table
+        .variant_columns(variant)
+        .into_iter()
+        .map(|(column, optional)| {
+          let value_type = value_type(column, config);
+          // Like the Rust patch types, a changeset can set a nullable column
+          // to null with Some(None) and leaves out anything that's None
+          let value_type = match (optional, column.nullable, variant) {
+            (true, true, Variant::Patch) => format!("Option<Option<{}>>", value_type),
+            (false, _, _) => value_type,
+            _ => format!("Option<{}>", value_type),
+          };
+          model_field(column, &value_type, config)
+        })
+        .collect();
+      items.push(model(
+        table,
+        &variants.name(variant, &name),
+        fields,
+        &[derive],
+        // so a changeset knows to leave the key alone
+        match variant {
+          Variant::Insert => &[],
+          Variant::Patch => &attributes,
+        },
+        config,
+      ));
+    }
+  }
+  items.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{config::Config, diesel::ToDiesel, Database};
+
+  #[test]
+  fn tables_become_table_macros_joined_by_their_foreign_keys() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+    t.string \"name\", null: false
+    t.integer \"logins\", limit: 2
+    t.decimal \"balance\", precision: 10, scale: 0
+    t.string \"type\"
+  end
+  create_table \"posts\", force: :cascade do |t|
+    t.bigint \"user_id\", null: false
+    t.bigint \"editor_id\"
+    t.string \"tags\", array: true, null: false
+    t.jsonb \"meta\"
+    t.datetime \"created_at\", null: false
+  end
+  create_table \"taggings\", id: false, force: :cascade do |t|
+    t.bigint \"post_id\"
+  end
+  add_foreign_key \"posts\", \"users\"
+  add_foreign_key \"posts\", \"users\", column: \"editor_id\"";
+    let mut config = Config::default();
+    config.diesel.models = false;
+    assert_eq!(
+      Database::from(schema).to_diesel(&config),
+      "// taggings has no primary key, so it can't be used with diesel::table!
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    users (id) {
+        id -> BigInt,
+        name -> Varchar,
+        logins -> Nullable<SmallInt>,
+        balance -> Nullable<Numeric>,
+        #[sql_name = \"type\"]
+        type_ -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    posts (id) {
+        id -> BigInt,
+        user_id -> BigInt,
+        editor_id -> Nullable<BigInt>,
+        tags -> Array<Varchar>,
+        meta -> Nullable<Jsonb>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::joinable!(posts -> users (user_id));
+
+diesel::allow_tables_to_appear_in_same_query!(
+    users,
+    posts,
+);"
+    );
+  }
+
+  #[test]
+  fn each_table_gets_queryable_insertable_and_changeset_models() {
+    let schema =
+      "create_table \"accounts\", id: :uuid, primary_key: \"uuid\", force: :cascade do |t|
+    t.string \"type\"
+    t.string \"name\", null: false
+    t.date \"opened_on\", default: -> { \"CURRENT_DATE\" }
+    t.datetime \"updated_at\", null: false
+  end";
+    assert_eq!(Database::from(schema).to_diesel(&Config::default()),
+      "use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    accounts (uuid) {
+        uuid -> Uuid,
+        #[sql_name = \"type\"]
+        type_ -> Nullable<Varchar>,
+        name -> Varchar,
+        opened_on -> Nullable<Date>,
+        updated_at -> Timestamp,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, diesel::Queryable, diesel::Selectable, diesel::Identifiable)]
+#[diesel(table_name = accounts)]
+#[diesel(primary_key(uuid))]
+pub struct Account {
+   pub uuid: Uuid,
+   #[diesel(column_name = type_)]
+   pub r#type: Option<String>,
+   pub name: String,
+   pub opened_on: Option<NaiveDate>,
+   pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, diesel::Insertable)]
+#[diesel(table_name = accounts)]
+pub struct NewAccount {
+   pub uuid: Uuid,
+   #[diesel(column_name = type_)]
+   pub r#type: Option<String>,
+   pub name: String,
+   pub opened_on: Option<NaiveDate>,
+   pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, diesel::AsChangeset)]
+#[diesel(table_name = accounts)]
+#[diesel(primary_key(uuid))]
+pub struct AccountChangeset {
+   pub uuid: Option<Uuid>,
+   #[diesel(column_name = type_)]
+   pub r#type: Option<Option<String>>,
+   pub name: Option<String>,
+   pub opened_on: Option<Option<NaiveDate>>,
+   pub updated_at: Option<NaiveDateTime>,
+}"
+    );
+  }
+
+  #[test]
+  fn postgres_enums_get_a_sql_type_and_a_rust_enum() {
+    let schema = "create_enum \"mood\", [\"happy\", \"not_sure\", \"very-sad\"]
+  create_table \"people\", force: :cascade do |t|
+    t.enum \"mood\", enum_type: \"mood\", null: false
+    t.enum \"moods\", enum_type: \"mood\", array: true
+  end";
+    let mut config = Config::default();
+    config.diesel.variants.enabled = false;
+    assert_eq!(Database::from(schema).to_diesel(&config),
+      "use serde::{Deserialize, Serialize};
+
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = \"mood\"))]
+    pub struct Mood;
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, diesel::AsExpression, diesel::FromSqlRow)]
+#[diesel(sql_type = sql_types::Mood)]
+#[serde(rename_all = \"snake_case\")]
+pub enum Mood {
+    Happy,
+    NotSure,
+    #[serde(rename = \"very-sad\")]
+    VerySad,
+}
+
+impl diesel::serialize::ToSql<sql_types::Mood, diesel::pg::Pg> for Mood {
+    fn to_sql<'b>(&'b self, out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>) -> diesel::serialize::Result {
+        let label = match self {
+            Mood::Happy => \"happy\",
+            Mood::NotSure => \"not_sure\",
+            Mood::VerySad => \"very-sad\",
+        };
+        std::io::Write::write_all(out, label.as_bytes())?;
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+impl diesel::deserialize::FromSql<sql_types::Mood, diesel::pg::Pg> for Mood {
+    fn from_sql(value: diesel::pg::PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        match std::str::from_utf8(value.as_bytes())? {
+            \"happy\" => Ok(Mood::Happy),
+            \"not_sure\" => Ok(Mood::NotSure),
+            \"very-sad\" => Ok(Mood::VerySad),
+            label => Err(format!(\"unrecognized mood: {}\", label).into()),
+        }
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Mood;
+
+    people (id) {
+        id -> BigInt,
+        mood -> Mood,
+        moods -> Nullable<Array<Mood>>,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, diesel::Queryable, diesel::Selectable, diesel::Identifiable)]
+#[diesel(table_name = people)]
+pub struct Person {
+   pub id: i64,
+   pub mood: Mood,
+   pub moods: Option<Vec<Mood>>,
+}"
+    );
+  }
+
+  #[test]
+  fn underscore_only_columns_are_escaped_like_rust_fields() {
+    let schema = "create_table \"marks\", force: :cascade do |t|
+    t.string \"_\", null: false
+    t.string \"__\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_diesel(&Config::default()),
+      "use serde::{Deserialize, Serialize};
+
+diesel::table! {
+    use diesel::sql_types::*;
+
+    marks (id) {
+        id -> BigInt,
+        #[sql_name = \"_\"]
+        __ -> Varchar,
+        #[sql_name = \"__\"]
+        ___ -> Varchar,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, diesel::Queryable, diesel::Selectable, diesel::Identifiable)]
+#[diesel(table_name = marks)]
+pub struct Mark {
+   pub id: i64,
+   #[serde(rename = \"_\")]
+   pub __: String,
+   #[serde(rename = \"__\")]
+   pub ___: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, diesel::Insertable)]
+#[diesel(table_name = marks)]
+pub struct NewMark {
+   #[serde(rename = \"_\")]
+   pub __: String,
+   #[serde(rename = \"__\")]
+   pub ___: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, diesel::AsChangeset)]
+#[diesel(table_name = marks)]
+pub struct MarkChangeset {
+   #[serde(rename = \"_\")]
+   pub __: Option<String>,
+   #[serde(rename = \"__\")]
+   pub ___: Option<String>,
+}"
+    );
+  }
+
+  #[test]
+  fn the_output_is_valid_rust() {
+    let schema = "create_enum \"status\", [\"draft\", \"published\"]
+  create_table \"type\", force: :cascade do |t|
+    t.enum \"status\", enum_type: \"status\", default: \"draft\", null: false
+    t.hstore \"settings\"
+    t.string \"123abc\"
+    t.float \"score\", null: false
+    t.time \"at\"
+    t.binary \"data\"
+    t.boolean \"active\", default: true
+    t.uuid \"token\"
+  end
+  create_table \"users\", force: :cascade do |t|
+    t.bigint \"type_id\"
+  end
+  add_foreign_key \"users\", \"type\"";
+    let diesel = Database::from(schema).to_diesel(&Config::default());
+    if let Err(error) = syn::parse_file(&diesel) {
+      panic!("{}\n\n{}", error, diesel);
+    }
+  }
+}
@@ -0,0 +1,510 @@
+use serde::Deserialize;
+
+use super::case::{is_identifier, Case};
+use super::config::Config;
+use super::typescript::{field_name, key, property_name};
+use super::ColumnData;
+use super::Database;
+use super::DefaultValue;
+use super::EnumType;
+use super::Index;
+use super::NumericType;
+use super::RailsColumn;
+use super::Table;
+use super::Variant;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DrizzleConfig {
+  // Declare datetime columns as `timestamp with time zone`, for apps that
+  // set Rails' `datetime_type` to `:timestamptz`
+  pub with_timezone: bool,
+}
+
+// Everything the schema can use from drizzle-orm/pg-core
+const PG_CORE: &[&str] = &[
+  "bigint",
+  "bigserial",
+  "boolean",
+  "customType",
+  "date",
+  "doublePrecision",
+  "index",
+  "integer",
+  "jsonb",
+  "numeric",
+  "pgEnum",
+  "pgTable",
+  "serial",
+  "smallint",
+  "smallserial",
+  "text",
+  "time",
+  "timestamp",
+  "uniqueIndex",
+  "uuid",
+  "varchar",
+];
+
+// Postgres types pg-core has no builder for, declared with `customType`
+const CUSTOM_TYPES: &[(&str, &str)] = &[
+  (
+    "bytea",
+    "const bytea = customType<{ data: Buffer }>({
+  dataType() {
+    return \"bytea\";
+  },
+});",
+  ),
+  (
+    "hstore",
+    "const hstore = customType<{ data: string }>({
+  dataType() {
+    return \"hstore\";
+  },
+});",
+  ),
+];
+
+pub trait ToDrizzle {
+  fn to_drizzle(&self, config: &Config) -> String;
+}
+
+impl ToDrizzle for Database {
+  fn to_drizzle(&self, config: &Config) -> String {
+    let enums = self
+      .enums()
+      .into_iter()
+      .filter(|enum_type| !enum_type.values.is_empty())
+      .map(|enum_type| {
+        format!(
+          "export const {} = pgEnum({:?}, [{}]);",
+          enum_name(enum_type),
+          enum_type.name,
+          values(&enum_type.values)
+        )
+      })
+      .collect::<Vec<String>>();
+    let tables = self
+      .0
+      .iter()
+      .map(|table| table_to_drizzle(table, self, config))
+      .collect::<Vec<String>>();
+    let body = [enums.clone(), tables.clone()].concat().join("\n\n");
+    let custom_types = CUSTOM_TYPES
+      .iter()
+      .filter(|(name, _)| calls(&body, name))
+      .map(|(_, declaration)| declaration.to_string())
+      .collect::<Vec<String>>();
+
+    let mut builders = PG_CORE
+      .iter()
+      .copied()
+      .filter(|name| calls(&body, name) || (*name == "customType" && !custom_types.is_empty()))
+      .collect::<Vec<&str>>();
+    if body.contains("AnyPgColumn") {
+      builders.insert(0, "type AnyPgColumn");
+    }
+    let mut imports = vec![];
+    if body.contains("sql`") {
+      imports.push("import { sql } from \"drizzle-orm\";".to_string());
+    }
+    if !builders.is_empty() {
+      imports.push(format!(
+        "import {{ {} }} from \"drizzle-orm/pg-core\";",
+        builders.join(", ")
+      ));
+    }
+
+    [vec![imports.join("\n")], custom_types, enums, tables]
+      .concat()
+      .into_iter()
+      .filter(|section| !section.is_empty())
+      .collect::<Vec<String>>()
+      .join("\n\n")
+  }
+}
+
+// Whether `body` calls the function `name`
+fn calls(body: &str, name: &str) -> bool {
+  let call = format!("{}(", name);
+  body.match_indices(&call).any(|(start, _)| {
+    !body[..start]
+      .chars()
+      .last()
+      .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.')
+  })
+}
+
+fn variable(name: &str) -> String {
+  let name = Case::Camel.convert(name, &[]);
+  if key(name.clone()) == name {
+    name
+  } else {
+    format!("_{}", name)
+  }
+}
+
+// A table's variable, named after the table like drizzle-kit does
+fn table_name(table: &Table) -> String {
+  variable(&table.name)
+}
+
+fn enum_name(enum_type: &EnumType) -> String {
+  variable(&format!("{}_enum", enum_type.name))
+}
+
+fn values(values: &[String]) -> String {
+  values
+    .iter()
+    .map(|value| format!("{:?}", value))
+    .collect::<Vec<String>>()
+    .join(", ")
+}
+
+// Reads a field off a table's columns, which can't always be done with a dot
+fn member(object: &str, field: &str) -> String {
+  if is_identifier(field) {
+    format!("{}.{}", object, field)
+  } else {
+    format!("{}[{:?}]", object, field)
+  }
+}
+
+fn field(column: &str, config: &Config) -> String {
+  property_name(column, config)
+}
+
+fn table_to_drizzle(table: &Table, database: &Database, config: &Config) -> String {
+  let name = table_name(table);
+  let columns = table.columns.iter().fold(String::new(), |columns, column| {
+    [
+      columns,
+      format!(
+        "  {}: {},",
+        field_name(&column.name, config),
+        column_to_drizzle(column, table, database, config)
+      ),
+    ]
+    .join("\n")
+  });
+  let indexes = table
+    .indexes
+    .iter()
+    .map(|index| format!("  {},", index_to_drizzle(index, table, config)))
+    .collect::<Vec<String>>();
+  let definition = if indexes.is_empty() {
+    format!(
+      "export const {} = pgTable({:?}, {{{}\n}});",
+      name, table.name, columns
+    )
+  } else {
+    format!(
+      "export const {} = pgTable({:?}, {{{}\n}}, (table) => [\n{}\n]);",
+      name,
+      table.name,
+      columns,
+      indexes.join("\n")
+    )
+  };
+  let type_name = table.type_name(config);
+  format!(
+    "{}\n\nexport type {} = typeof {}.$inferSelect;\nexport type {} = typeof {}.$inferInsert;",
+    definition,
+    type_name,
+    name,
+    config.typescript.variants.name(Variant::Insert, &type_name),
+    name
+  )
+}
+
+// Indexes on an expression rather than columns are left as a comment
+fn index_to_drizzle(index: &Index, table: &Table, config: &Config) -> String {
+  let name = index
+    .name
+    .clone()
+    .unwrap_or_else(|| format!("index_{}_on_{}", table.name, index.columns.join("_and_")));
+  let known = index
+    .columns
+    .iter()
+    .all(|name| table.columns.iter().any(|column| &column.name == name));
+  if !known {
+    return format!("// {} is on an expression drizzle can't describe", name);
+  }
+  let columns = index
+    .columns
+    .iter()
+    .map(|column| member("table", &field(column, config)))
+    .collect::<Vec<String>>()
+    .join(", ");
+  let builder = if index.unique { "uniqueIndex" } else { "index" };
+  format!("{}({:?}).on({})", builder, name, columns)
+}
+
+// The options object a builder takes, left out when it would be empty
+fn with_options(builder: &str, name: &str, options: Vec<String>) -> String {
+  if options.is_empty() {
+    format!("{}({:?})", builder, name)
+  } else {
+    format!("{}({:?}, {{ {} }})", builder, name, options.join(", "))
+  }
+}
+
+fn builder(column: &ColumnData, config: &Config) -> String {
+  let options = &column.options;
+  let name = column.name.as_str();
+  let precision = options
+    .precision
+    .map(|precision| format!("precision: {}", precision));
+  let allowed_values = if options.allowed_values.is_empty() {
+    None
+  } else {
+    Some(format!("enum: [{}]", values(&options.allowed_values)))
+  };
+  let auto_increment = column.is_primary_key()
+    && options.default.is_none()
+    && matches!(
+      column.value_type,
+      RailsColumn::PrimaryKey | RailsColumn::Integer | RailsColumn::Bigint
+    );
+  match (&column.value_type, column.numeric_type(config.adapter)) {
+    (_, Some(NumericType::SmallInt)) if auto_increment => format!("smallserial({:?})", name),
+    (_, Some(NumericType::Int)) if auto_increment => format!("serial({:?})", name),
+    (_, Some(NumericType::BigInt)) if auto_increment => {
+      format!("bigserial({:?}, {{ mode: \"number\" }})", name)
+    }
+    (RailsColumn::Decimal | RailsColumn::Numeric, _) => with_options(
+      "numeric",
+      name,
+      precision
+        .into_iter()
+        .chain(options.scale.map(|scale| format!("scale: {}", scale)))
+        .collect(),
+    ),
+    (_, Some(NumericType::SmallInt)) => format!("smallint({:?})", name),
+    (_, Some(NumericType::Int)) => format!("integer({:?})", name),
+    (_, Some(NumericType::BigInt)) => format!("bigint({:?}, {{ mode: \"number\" }})", name),
+    (_, Some(NumericType::Float)) => format!("doublePrecision({:?})", name),
+    (_, Some(NumericType::Decimal)) => unreachable!("decimal columns are handled above"),
+    (RailsColumn::String, None) => with_options(
+      "varchar",
+      name,
+      options
+        .limit
+        .map(|limit| format!("length: {}", limit))
+        .into_iter()
+        .chain(allowed_values)
+        .collect(),
+    ),
+    (RailsColumn::Text, None) => with_options("text", name, allowed_values.into_iter().collect()),
+    (RailsColumn::Datetime, None) => {
+      let timezone = config
+        .drizzle
+        .with_timezone
+        .then(|| "withTimezone: true".to_string());
+      with_options(
+        "timestamp",
+        name,
+        precision.into_iter().chain(timezone).collect(),
+      )
+    }
+    (RailsColumn::Time, None) => with_options("time", name, precision.into_iter().collect()),
+    (RailsColumn::Date, None) => format!("date({:?})", name),
+    (RailsColumn::Binary, None) => format!("bytea({:?})", name),
+    (RailsColumn::Boolean, None) => format!("boolean({:?})", name),
+    (RailsColumn::HStore, None) => format!("hstore({:?})", name),
+    (RailsColumn::JsonB, None) => format!("jsonb({:?})", name),
+    (RailsColumn::Uuid, None) => format!("uuid({:?})", name),
+    (RailsColumn::Enum, None) => match &options.enum_type {
+      Some(enum_type) if !enum_type.values.is_empty() => {
+        format!("{}({:?})", enum_name(enum_type), name)
+      }
+      _ => format!("text({:?})", name),
+    },
+    (RailsColumn::PrimaryKey | RailsColumn::Integer | RailsColumn::Bigint, None)
+    | (RailsColumn::Float, None) => unreachable!("numeric columns have a numeric type"),
+  }
+}
+
+// Expression defaults drizzle has a method for, and anything else as raw SQL
+fn default(column: &ColumnData, value: &DefaultValue) -> Option<String> {
+  let literal = match (value, &column.value_type) {
+    (DefaultValue::Expression(expression), _) => {
+      return Some(match (expression.as_str(), &column.value_type) {
+        ("now()" | "CURRENT_TIMESTAMP", _) => ".defaultNow()".to_string(),
+        ("gen_random_uuid()" | "uuid_generate_v4()", RailsColumn::Uuid) => {
+          ".defaultRandom()".to_string()
+        }
+        (expression, _) => format!(".default(sql`{}`)", expression.replace('`', "\\`")),
+      });
+    }
+    // Numeric columns are read as strings so their values can't lose precision
+    (value, RailsColumn::Decimal | RailsColumn::Numeric) => format!("{:?}", value.as_number()?),
+    (DefaultValue::String(string), RailsColumn::Datetime) => format!("new Date({:?})", string),
+    (value, _) => value.to_json()?,
+  };
+  Some(format!(".default({})", literal))
+}
+
+fn column_to_drizzle(
+  column: &ColumnData,
+  table: &Table,
+  database: &Database,
+  config: &Config,
+) -> String {
+  let mut builder = builder(column, config);
+  if column.options.array {
+    builder.push_str(".array()");
+  }
+  if column.is_primary_key() {
+    builder.push_str(".primaryKey()");
+  } else if !column.nullable {
+    builder.push_str(".notNull()");
+  }
+  if let Some(default) = column
+    .options
+    .default
+    .as_ref()
+    .and_then(|value| default(column, value))
+  {
+    builder.push_str(&default);
+  }
+  let parent = column.options.foreign_key.as_ref().and_then(|foreign_key| {
+    database
+      .0
+      .iter()
+      .find(|parent| parent.name == foreign_key.table)
+      .map(|parent| (parent, &foreign_key.primary_key))
+  });
+  if let Some((parent, primary_key)) = parent {
+    let target = member(&table_name(parent), &field(primary_key, config));
+    // A table pointing at itself has to spell out the column's type
+    if parent.name == table.name {
+      builder.push_str(&format!(".references((): AnyPgColumn => {})", target));
+    } else {
+      builder.push_str(&format!(".references(() => {})", target));
+    }
+  }
+  builder
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{config::Config, drizzle::ToDrizzle, Database};
+
+  #[test]
+  fn tables_become_pg_tables_with_enums_references_and_indexes() {
+    let schema = "create_enum \"mood\", [\"happy\", \"sad\"]
+  create_table \"users\", force: :cascade do |t|
+    t.string \"email\", limit: 255, null: false
+    t.string \"role\", default: \"member\", null: false
+    t.enum \"mood\", enum_type: \"mood\"
+    t.string \"tags\", array: true, default: [], null: false
+    t.decimal \"balance\", precision: 10, scale: 2, default: \"0.0\", null: false
+    t.boolean \"admin\", default: false, null: false
+    t.jsonb \"settings\", default: {}, null: false
+    t.datetime \"created_at\", null: false
+    t.index [\"email\"], name: \"index_users_on_email\", unique: true
+    t.check_constraint \"role IN ('member', 'admin')\", name: \"role_check\"
+  end
+  create_table \"posts\", id: :uuid, default: -> { \"gen_random_uuid()\" }, force: :cascade do |t|
+    t.bigint \"user_id\", null: false
+    t.uuid \"parent_id\"
+    t.integer \"position\", limit: 2
+    t.datetime \"published_at\", precision: 3, default: -> { \"now()\" }
+    t.index [\"user_id\", \"position\"]
+  end
+  add_foreign_key \"posts\", \"users\"
+  add_foreign_key \"posts\", \"posts\", column: \"parent_id\"";
+    assert_eq!(
+      Database::from(schema).to_drizzle(&Config::default()),
+      "import { type AnyPgColumn, bigint, bigserial, boolean, index, jsonb, numeric, pgEnum, pgTable, smallint, timestamp, uniqueIndex, uuid, varchar } from \"drizzle-orm/pg-core\";
+
+export const moodEnum = pgEnum(\"mood\", [\"happy\", \"sad\"]);
+
+export const users = pgTable(\"users\", {
+  id: bigserial(\"id\", { mode: \"number\" }).primaryKey(),
+  email: varchar(\"email\", { length: 255 }).notNull(),
+  role: varchar(\"role\", { enum: [\"member\", \"admin\"] }).notNull().default(\"member\"),
+  mood: moodEnum(\"mood\"),
+  tags: varchar(\"tags\").array().notNull().default([]),
+  balance: numeric(\"balance\", { precision: 10, scale: 2 }).notNull().default(\"0.0\"),
+  admin: boolean(\"admin\").notNull().default(false),
+  settings: jsonb(\"settings\").notNull().default({}),
+  created_at: timestamp(\"created_at\").notNull(),
+}, (table) => [
+  uniqueIndex(\"index_users_on_email\").on(table.email),
+]);
+
+export type User = typeof users.$inferSelect;
+export type NewUser = typeof users.$inferInsert;
+
+export const posts = pgTable(\"posts\", {
+  id: uuid(\"id\").primaryKey().defaultRandom(),
+  user_id: bigint(\"user_id\", { mode: \"number\" }).notNull().references(() => users.id),
+  parent_id: uuid(\"parent_id\").references((): AnyPgColumn => posts.id),
+  position: smallint(\"position\"),
+  published_at: timestamp(\"published_at\", { precision: 3 }).defaultNow(),
+}, (table) => [
+  index(\"index_posts_on_user_id_and_position\").on(table.user_id, table.position),
+]);
+
+export type Post = typeof posts.$inferSelect;
+export type NewPost = typeof posts.$inferInsert;"
+    );
+  }
+
+  #[test]
+  fn datetimes_can_have_a_time_zone() {
+    let schema = "create_table \"events\", id: :serial, force: :cascade do |t|
+    t.datetime \"starts_at\", null: false
+    t.binary \"data\"
+  end";
+    let mut config = Config::default();
+    config.drizzle.with_timezone = true;
+    assert_eq!(
+      Database::from(schema).to_drizzle(&config),
+      "import { customType, pgTable, serial, timestamp } from \"drizzle-orm/pg-core\";
+
+const bytea = customType<{ data: Buffer }>({
+  dataType() {
+    return \"bytea\";
+  },
+});
+
+export const events = pgTable(\"events\", {
+  id: serial(\"id\").primaryKey(),
+  starts_at: timestamp(\"starts_at\", { withTimezone: true }).notNull(),
+  data: bytea(\"data\"),
+});
+
+export type Event = typeof events.$inferSelect;
+export type NewEvent = typeof events.$inferInsert;"
+    );
+  }
+
+  #[test]
+  fn the_output_is_valid_typescript() {
+    let schema = "create_enum \"status\", [\"draft\", \"published\"]
+  create_table \"2fa_codes\", force: :cascade do |t|
+    t.enum \"status\", enum_type: \"status\", array: true, null: false
+    t.hstore \"settings\"
+    t.string \"default\", limit: 10
+    t.string \"123abc\"
+    t.text \"kind\"
+    t.time \"at\"
+    t.date \"on\", default: \"2020-01-01\"
+    t.float \"score\", default: 1.5
+    t.datetime \"created_at\", default: -> { \"CURRENT_TIMESTAMP\" }, null: false
+    t.index \"lower((kind)::text)\", name: \"index_codes_on_lower_kind\"
+    t.index [\"default\", \"123abc\"]
+    t.check_constraint \"kind IN ('a', 'b')\"
+  end";
+    let drizzle = Database::from(schema).to_drizzle(&Config::default());
+    let allocator = oxc_allocator::Allocator::default();
+    let parsed = oxc_parser::Parser::new(&allocator, &drizzle, oxc_span::SourceType::ts()).parse();
+    if let Some(error) = parsed.errors.first() {
+      panic!("{}\n\n{}", error, drizzle);
+    }
+  }
+}
@@ -1,74 +1,696 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Deserializer};
+
 use super::ColumnData;
 
+use super::case::{is_identifier, Case};
+use super::config::{Adapter, Config, VariantConfig};
+use super::Association;
+use super::AssociationKind;
 use super::Database;
+use super::DefaultValue;
+use super::NumericType;
 use super::RailsColumn;
 use super::Table;
+use super::Variant;
 
-pub trait ToGo {
-  fn to_go(&self) -> String;
+// How decimal columns are represented in Go
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GoDecimal {
+  // github.com/shopspring/decimal
+  #[default]
+  Decimal,
+  String,
+  Float64,
 }
 
-impl ToGo for Database {
-  fn to_go(&self) -> String {
-    self.0.iter().fold(String::new(), |specs, table| {
-      if specs.is_empty() {
-        [specs, table.to_go()].join("")
-      } else {
-        [specs, table.to_go()].join("\n\n")
-      }
+// How date and time columns are represented in Go. Datetimes are always a time.Time.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GoDateTime {
+  #[default]
+  Time,
+  // cloud.google.com/go/civil
+  Civil,
+}
+
+// How nullable columns are represented in Go. Maps and slices can
+// already be nil so they're left alone.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GoNullable {
+  #[default]
+  Pointer,
+  SqlNull,
+  Value,
+}
+
+// The library the structs are read from the database with, if any
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GoFlavour {
+  #[default]
+  Plain,
+  Gorm,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct GoConfig {
+  // The package clause at the top of the file
+  pub package: String,
+  pub decimal: GoDecimal,
+  pub date_time: GoDateTime,
+  pub nullable: GoNullable,
+  // Emit a `NewTable()` constructor for tables with column defaults
+  pub defaults: bool,
+  #[serde(deserialize_with = "variants")]
+  pub variants: VariantConfig,
+  // With the gorm flavour, fields are tagged for GORM instead of sqlx and
+  // foreign keys become association fields
+  pub flavour: GoFlavour,
+}
+
+impl Default for GoConfig {
+  fn default() -> Self {
+    GoConfig {
+      package: "models".to_string(),
+      decimal: GoDecimal::default(),
+      date_time: GoDateTime::default(),
+      nullable: GoNullable::default(),
+      defaults: true,
+      variants: GoVariantConfig::default().into(),
+      flavour: GoFlavour::default(),
+    }
+  }
+}
+
+// The `[go.variants]` table, read on its own so the insert name it falls
+// back to isn't the NewTable already taken by the constructor
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+struct GoVariantConfig {
+  enabled: bool,
+  insert: String,
+  patch: String,
+}
+
+impl Default for GoVariantConfig {
+  fn default() -> Self {
+    GoVariantConfig {
+      enabled: false,
+      insert: "{}Insert".to_string(),
+      patch: "{}Patch".to_string(),
+    }
+  }
+}
+
+impl From<GoVariantConfig> for VariantConfig {
+  fn from(variants: GoVariantConfig) -> Self {
+    VariantConfig {
+      enabled: variants.enabled,
+      insert: variants.insert,
+      patch: variants.patch,
+    }
+  }
+}
+
+fn variants<'de, D: Deserializer<'de>>(deserializer: D) -> Result<VariantConfig, D::Error> {
+  GoVariantConfig::deserialize(deserializer).map(VariantConfig::from)
+}
+
+// Initialisms Go keeps in capitals, like the ID in UserID, from the list
+// the standard linters check for. Any `acronyms` in the config come first.
+const INITIALISMS: &[&str] = &[
+  "ACL", "API", "ASCII", "CPU", "CSS", "DNS", "EOF", "GUID", "HTML", "HTTP", "HTTPS", "ID", "IP",
+  "JSON", "QPS", "RAM", "RPC", "SLA", "SMTP", "SQL", "SSH", "TCP", "TLS", "TTL", "UDP", "UI",
+  "UID", "UUID", "URI", "URL", "UTF8", "VM", "XML", "XMPP", "XSRF", "XSS",
+];
+
+fn acronyms(config: &Config) -> Vec<String> {
+  config
+    .acronyms
+    .iter()
+    .cloned()
+    .chain(INITIALISMS.iter().map(|initialism| initialism.to_string()))
+    .collect()
+}
+
+// Names that can't start a Go identifier are prefixed with an X, which
+// keeps them exported
+fn exported(name: String) -> String {
+  if is_identifier(&name) {
+    name
+  } else {
+    format!("X{}", name)
+  }
+}
+
+fn type_name(table: &Table, config: &Config) -> String {
+  exported(Case::Pascal.convert(&table.record_name(config), &acronyms(config)))
+}
+
+// Packages a generated type can refer to, keyed by the name it's used under
+const PACKAGES: &[(&str, &str)] = &[
+  ("sql", "database/sql"),
+  ("time", "time"),
+  ("civil", "cloud.google.com/go/civil"),
+  ("decimal", "github.com/shopspring/decimal"),
+  ("gorm", "gorm.io/gorm"),
+];
+
+// Finds the packages used by each column's type. The standard library is
+// grouped ahead of everything else, the same as goimports would.
+fn imports(database: &Database, config: &Config) -> String {
+  let packages = database
+    .0
+    .iter()
+    .flat_map(|table| table.columns.iter())
+    .map(|column| value_type(column, config))
+    .flat_map(|value_type| {
+      PACKAGES
+        .iter()
+        .filter(move |(name, _)| value_type.contains(&format!("{}.", name)))
+        .map(|(_, path)| *path)
     })
+    .collect::<BTreeSet<&str>>();
+  if packages.is_empty() {
+    return String::new();
   }
+
+  let (standard, external): (Vec<&str>, Vec<&str>) =
+    packages.into_iter().partition(|path| !path.contains('.'));
+  let groups = [standard, external]
+    .iter()
+    .filter(|group| !group.is_empty())
+    .map(|group| {
+      group
+        .iter()
+        .map(|path| format!("\t\"{}\"", path))
+        .collect::<Vec<String>>()
+        .join("\n")
+    })
+    .collect::<Vec<String>>();
+  format!("import (\n{}\n)", groups.join("\n\n"))
 }
-fn format_name(name: &str) -> String {
-  name
-    .split('_')
-    .map(|n| n.chars().next().unwrap().to_uppercase().to_string() + &n[1..])
+
+pub trait ToGo {
+  fn to_go(&self, config: &Config) -> String;
+}
+
+// Returns a pointer to a copy of a value, for constructors to fill in
+// pointer fields with a default
+const PTR: &str = "func ptr[T any](value T) *T {
+\treturn &value
+}";
+
+impl ToGo for Database {
+  fn to_go(&self, config: &Config) -> String {
+    let tables = self
+      .0
+      .iter()
+      .map(|table| {
+        let associations = match config.go.flavour {
+          GoFlavour::Plain => vec![],
+          GoFlavour::Gorm => self.associations(table),
+        };
+        table_to_go(table, &associations, config)
+      })
+      .collect::<Vec<String>>()
+      .join("\n\n");
+    let ptr = if tables.contains("ptr[") { PTR } else { "" };
+    [
+      format!("package {}", config.go.package),
+      imports(self, config),
+      tables,
+      ptr.to_string(),
+    ]
+    .into_iter()
+    .filter(|section| !section.is_empty())
     .collect::<Vec<String>>()
-    .join("")
+    .join("\n\n")
+  }
 }
 
-impl ToGo for Table {
-  fn to_go(&self) -> String {
-    let spec = self.columns.iter().fold(String::new(), |spec, column| {
-      [spec, "    ".to_owned() + &column.to_go()].join("\n")
+// Fields are always exported in PascalCase, so encoding/json, sqlx and GORM
+// can see them, and their tags carry the column's name. Go's keywords are all
+// lower case, so they never clash.
+fn field_name(name: &str, config: &Config) -> String {
+  exported(Case::Pascal.convert(name, &acronyms(config)))
+}
+
+// A field's name, type and tags. Every field is tagged with its column, for
+// encoding/json and for sqlx and friends, or for GORM along with everything
+// it needs to know to migrate the table.
+fn field(
+  column: &ColumnData,
+  value_type: &str,
+  table: Option<&Table>,
+  config: &Config,
+) -> [String; 3] {
+  tagged_field(column, value_type, false, table, config)
+}
+
+// A field that encoding/json can leave out when it's nil or empty
+fn tagged_field(
+  column: &ColumnData,
+  value_type: &str,
+  omit_empty: bool,
+  table: Option<&Table>,
+  config: &Config,
+) -> [String; 3] {
+  let json = if omit_empty {
+    format!("{},omitempty", column.name)
+  } else {
+    column.name.clone()
+  };
+  let tags = match config.go.flavour {
+    GoFlavour::Plain => format!("`json:\"{}\" db:\"{}\"`", json, column.name),
+    GoFlavour::Gorm => format!(
+      "`json:\"{}\" gorm:\"{}\"`",
+      json,
+      gorm_tag(column, table, config)
+    ),
+  };
+  [
+    field_name(&column.name, config),
+    value_type.to_string(),
+    tags,
+  ]
+}
+
+// The column's SQL type, for the columns GORM can't work out from their Go type
+fn sql_type(column: &ColumnData, config: &Config) -> Option<String> {
+  let options = &column.options;
+  let mysql = config.adapter == Adapter::Mysql;
+  let decimal = if mysql { "decimal" } else { "numeric" };
+  let sql_type = match (&column.value_type, config.adapter) {
+    (RailsColumn::String, _) => match options.limit {
+      Some(limit) => format!("varchar({})", limit),
+      None => "varchar".to_string(),
+    },
+    (RailsColumn::Decimal | RailsColumn::Numeric, _) => match (options.precision, options.scale) {
+      (Some(precision), Some(scale)) => format!("{}({},{})", decimal, precision, scale),
+      (Some(precision), None) => format!("{}({})", decimal, precision),
+      _ => decimal.to_string(),
+    },
+    (RailsColumn::Enum, _) => match &options.enum_type {
+      Some(enum_type) => enum_type.name.clone(),
+      None => "varchar".to_string(),
+    },
+    (RailsColumn::JsonB, Adapter::Postgresql) => "jsonb".to_string(),
+    (RailsColumn::JsonB, _) => "json".to_string(),
+    (RailsColumn::HStore, _) => "hstore".to_string(),
+    (RailsColumn::Uuid, _) => "uuid".to_string(),
+    (RailsColumn::Text, _) => "text".to_string(),
+    (RailsColumn::Boolean, _) => "boolean".to_string(),
+    (RailsColumn::Datetime, Adapter::Mysql) => "datetime".to_string(),
+    (RailsColumn::Datetime, _) => "timestamp".to_string(),
+    (RailsColumn::Date, _) => "date".to_string(),
+    (RailsColumn::Time, _) => "time".to_string(),
+    (RailsColumn::Binary, Adapter::Postgresql) => "bytea".to_string(),
+    (RailsColumn::Binary, _) => "blob".to_string(),
+    (_, _) => match column.numeric_type(config.adapter) {
+      Some(NumericType::SmallInt) => "smallint".to_string(),
+      Some(NumericType::Int) => "integer".to_string(),
+      Some(NumericType::BigInt) => "bigint".to_string(),
+      Some(NumericType::Float) if mysql => "double".to_string(),
+      Some(NumericType::Float) => "double precision".to_string(),
+      _ => return None,
+    },
+  };
+  let needed = options.array
+    || match column.value_type {
+      RailsColumn::String => options.limit.is_some(),
+      RailsColumn::Decimal | RailsColumn::Numeric => options.precision.is_some(),
+      RailsColumn::Enum | RailsColumn::JsonB | RailsColumn::HStore | RailsColumn::Uuid => true,
+      _ => false,
+    };
+  match (needed, options.array) {
+    (false, _) => None,
+    (true, true) => Some(format!("{}[]", sql_type)),
+    (true, false) => Some(sql_type),
+  }
+}
+
+// A default as GORM passes it on to the database. Anything that would need
+// escaping in a tag is left for the database to fill in.
+fn gorm_default(value: &DefaultValue) -> Option<String> {
+  let default = match value {
+    DefaultValue::Boolean(boolean) => boolean.to_string(),
+    DefaultValue::Integer(integer) => integer.to_string(),
+    DefaultValue::Float(float) => float.to_string(),
+    DefaultValue::String(string) => format!("'{}'", string.replace('\'', "''")),
+    DefaultValue::Expression(expression) => expression.clone(),
+    DefaultValue::Array(_) | DefaultValue::Hash(_) => return None,
+  };
+  if default.contains([';', '"', '\\', '`']) {
+    None
+  } else {
+    Some(default)
+  }
+}
+
+fn gorm_tag(column: &ColumnData, table: Option<&Table>, config: &Config) -> String {
+  let mut settings = vec![format!("column:{}", column.name)];
+  settings.extend(sql_type(column, config).map(|sql_type| format!("type:{}", sql_type)));
+  // GORM can't scan JSON into a map by itself
+  if column.value_type == RailsColumn::JsonB && !column.options.array {
+    settings.push("serializer:json".to_string());
+  }
+  if column.is_primary_key() {
+    settings.push("primaryKey".to_string());
+  } else if !column.nullable {
+    settings.push("not null".to_string());
+  }
+  settings.extend(
+    column
+      .options
+      .default
+      .as_ref()
+      .and_then(gorm_default)
+      .map(|default| format!("default:{}", default)),
+  );
+  // GORM builds one index out of every field that names it, in order of priority
+  let indexes = table.map_or(&[][..], |table| &table.indexes);
+  for index in indexes {
+    let Some(position) = index.columns.iter().position(|name| *name == column.name) else {
+      continue;
+    };
+    let kind = if index.unique { "uniqueIndex" } else { "index" };
+    let name = index.name.clone().unwrap_or_else(|| {
+      let table_name = table.map_or("", |table| table.name.as_str());
+      format!("index_{}_on_{}", table_name, index.columns.join("_and_"))
     });
+    if index.columns.len() > 1 {
+      settings.push(format!("{}:{},priority:{}", kind, name, position + 1));
+    } else {
+      settings.push(format!("{}:{}", kind, name));
+    }
+  }
+  settings.join(";")
+}
+
+// A belongs_to holds a pointer so that a struct can refer to its own type
+fn association_field(association: &Association, config: &Config) -> [String; 3] {
+  let name = type_name(association.table, config);
+  let value_type = match association.kind {
+    AssociationKind::BelongsTo => format!("*{}", name),
+    AssociationKind::HasMany => format!("[]{}", name),
+  };
+  [
+    field_name(&association.name, config),
+    value_type,
+    format!(
+      "`json:\"{},omitempty\" gorm:\"foreignKey:{}\"`",
+      association.name,
+      field_name(&association.column.name, config)
+    ),
+  ]
+}
+
+// GORM looks for a model's table under its name in snake_case, pluralised,
+// so any other table has to be named
+fn table_name_method(table: &Table, name: &str, config: &Config) -> Option<String> {
+  let conventional = config
+    .inflections
+    .pluralize(&Case::Snake.convert(name, &[]));
+  if conventional == table.name {
+    return None;
+  }
+  Some(format!(
+    "func ({}) TableName() string {{\n\treturn {:?}\n}}",
+    name, table.name
+  ))
+}
+
+// A struct with its names, types and tags lined up in columns, as gofmt would
+fn structure(name: &str, fields: &[[String; 3]]) -> String {
+  let width = |cell: usize| {
+    fields
+      .iter()
+      .map(|field| field[cell].chars().count())
+      .max()
+      .unwrap_or(0)
+  };
+  let (name_width, type_width) = (width(0), width(1));
+  let fields = fields
+    .iter()
+    .map(|[field_name, value_type, tags]| {
+      format!(
+        "\n\t{:<name_width$} {:<type_width$} {}",
+        field_name, value_type, tags
+      )
+    })
+    .collect::<String>();
+  format!("type {} struct {{{}\n}}", name, fields)
+}
+
+// A table on its own doesn't know what points at it, so it has no associations
+impl ToGo for Table {
+  fn to_go(&self, config: &Config) -> String {
+    table_to_go(self, &[], config)
+  }
+}
+
+fn table_to_go(table: &Table, associations: &[Association], config: &Config) -> String {
+  let fields = table
+    .columns
+    .iter()
+    .map(|column| column_field(column, Some(table), config))
+    .chain(
+      associations
+        .iter()
+        .map(|association| association_field(association, config)),
+    )
+    .collect::<Vec<[String; 3]>>();
 
-    let name = format_name(&self.name);
-    format!("type {} struct {{{}\n}}", name, spec)
+  let name = type_name(table, config);
+  let mut items = vec![structure(&name, &fields)];
+  if config.go.flavour == GoFlavour::Gorm {
+    items.extend(table_name_method(table, &name, config));
+  }
+  if config.go.defaults && table.has_literal_defaults() {
+    items.push(constructor(table, &name, config));
+  }
+  if config.go.variants.enabled {
+    items.push(variant(table, &name, Variant::Insert, config));
+    items.push(variant(table, &name, Variant::Patch, config));
+  }
+  items.join("\n\n")
+}
+
+// Columns that can be left out are pointers, where nil means "not given",
+// and are left out of the JSON too. Maps and slices are nilable already, but
+// omitempty would also drop an empty one that was given on purpose.
+fn variant_field(
+  column: &ColumnData,
+  optional: bool,
+  table: &Table,
+  config: &Config,
+) -> [String; 3] {
+  if !optional {
+    return column_field(column, Some(table), config);
+  }
+  let base_type = base_type(column, config);
+  let value_type = if is_nilable(&base_type) {
+    value_type(column, config)
+  } else {
+    format!("*{}", base_type)
+  };
+  let omit_empty = value_type.starts_with('*');
+  tagged_field(column, &value_type, omit_empty, Some(table), config)
+}
+
+fn variant(table: &Table, name: &str, variant: Variant, config: &Config) -> String {
+  let fields = table
+    .variant_columns(variant)
+    .into_iter()
+    .map(|(column, optional)| variant_field(column, optional, table, config))
+    .collect::<Vec<[String; 3]>>();
+  structure(&config.go.variants.name(variant, name), &fields)
+}
+
+// Renders a literal default as a value of the column's Go type
+fn default_literal(value: &DefaultValue, value_type: &str) -> Option<String> {
+  match (value, value_type) {
+    (DefaultValue::Boolean(boolean), "bool") => Some(boolean.to_string()),
+    (DefaultValue::Integer(integer), "int16" | "int32" | "int64") => Some(integer.to_string()),
+    (value, "float64") => value.as_number(),
+    (DefaultValue::String(string), "string") => Some(format!("{:?}", string)),
+    (value, "decimal.Decimal") => Some(format!(
+      "decimal.RequireFromString({:?})",
+      value.as_number()?
+    )),
+    (DefaultValue::Hash(pairs), "map[string]interface{}") if pairs.is_empty() => {
+      Some("map[string]interface{}{}".to_string())
+    }
+    _ => None,
+  }
+}
+
+// Sets the fields with a literal default, the rest are left as Go's zero
+// value. Expression defaults are left for the database to fill in.
+fn constructor(table: &Table, name: &str, config: &Config) -> String {
+  let values = table
+    .columns
+    .iter()
+    .filter_map(|column| {
+      let base_type = base_type(column, config);
+      let literal = default_literal(&column.typed_default(config.adapter)?, &base_type)?;
+      let value = match (value_type(column, config), config.go.nullable) {
+        (value_type, _) if value_type == base_type => literal,
+        (_, GoNullable::SqlNull) => match SQL_NULL_TYPES.iter().find(|(go, _, _)| *go == base_type)
+        {
+          Some((_, null_type, field)) => {
+            format!("{}{{{}: {}, Valid: true}}", null_type, field, literal)
+          }
+          None => format!("sql.Null[{}]{{V: {}, Valid: true}}", base_type, literal),
+        },
+        // Naming the type keeps untyped constants from becoming an int or a float64
+        _ => format!("ptr[{}]({})", base_type, literal),
+      };
+      Some((format!("{}:", field_name(&column.name, config)), value))
+    })
+    .collect::<Vec<(String, String)>>();
+  // gofmt lines up the values too
+  let width = values
+    .iter()
+    .map(|(key, _)| key.chars().count())
+    .max()
+    .unwrap_or(0);
+  let fields = values
+    .iter()
+    .map(|(key, value)| format!("\n\t\t{:<width$} {},", key, value))
+    .collect::<String>();
+  format!(
+    "func New{}() {} {{\n\treturn {}{{{}\n\t}}\n}}",
+    name, name, name, fields
+  )
+}
+
+impl ToGo for NumericType {
+  fn to_go(&self, config: &Config) -> String {
+    match (self, config.go.decimal) {
+      (NumericType::SmallInt, _) => "int16".to_string(),
+      (NumericType::Int, _) => "int32".to_string(),
+      (NumericType::BigInt, _) => "int64".to_string(),
+      (NumericType::Float, _) => "float64".to_string(),
+      (NumericType::Decimal, GoDecimal::Decimal) => "decimal.Decimal".to_string(),
+      (NumericType::Decimal, GoDecimal::String) => "string".to_string(),
+      (NumericType::Decimal, GoDecimal::Float64) => "float64".to_string(),
+    }
   }
 }
 
 impl ToGo for RailsColumn {
-  fn to_go(&self) -> String {
+  fn to_go(&self, config: &Config) -> String {
+    if let Some(numeric_type) = self.numeric_type(config.adapter) {
+      return numeric_type.to_go(config);
+    }
     match self {
-      RailsColumn::PrimaryKey => "*int64".to_string(),
-      RailsColumn::String => "*string".to_string(),
-      RailsColumn::Text => "*string".to_string(),
-      RailsColumn::Integer => "*int64".to_string(),
-      RailsColumn::Bigint => "*int128".to_string(),
-      RailsColumn::Float => "*float64".to_string(),
-      RailsColumn::Decimal => "*float64".to_string(),
-      RailsColumn::Numeric => "*int64".to_string(),
-      RailsColumn::Datetime => "*time.Time".to_string(),
-      RailsColumn::Time => "*time.Time".to_string(),
-      RailsColumn::Date => "*time.Time".to_string(),
-      RailsColumn::Binary => "*[]uint8".to_string(),
-      RailsColumn::Boolean => "*bool".to_string(),
+      RailsColumn::PrimaryKey
+      | RailsColumn::Integer
+      | RailsColumn::Bigint
+      | RailsColumn::Float
+      | RailsColumn::Decimal
+      | RailsColumn::Numeric => unreachable!("numeric columns are handled above"),
+      RailsColumn::String => "string".to_string(),
+      RailsColumn::Text => "string".to_string(),
+      RailsColumn::Datetime => "time.Time".to_string(),
+      RailsColumn::Time => match config.go.date_time {
+        GoDateTime::Time => "time.Time".to_string(),
+        GoDateTime::Civil => "civil.Time".to_string(),
+      },
+      RailsColumn::Date => match config.go.date_time {
+        GoDateTime::Time => "time.Time".to_string(),
+        GoDateTime::Civil => "civil.Date".to_string(),
+      },
+      RailsColumn::Binary => "[]byte".to_string(),
+      RailsColumn::Boolean => "bool".to_string(),
       RailsColumn::HStore => "map[string]interface{}".to_string(),
       RailsColumn::JsonB => "map[string]interface{}".to_string(),
+      RailsColumn::Uuid => "string".to_string(),
+      RailsColumn::Enum => "string".to_string(),
     }
   }
 }
 
+// The nullable wrapper for each type, along with the field that holds its value.
+// Anything else falls back to the generic sql.Null[T].
+const SQL_NULL_TYPES: &[(&str, &str, &str)] = &[
+  ("string", "sql.NullString", "String"),
+  ("int16", "sql.NullInt16", "Int16"),
+  ("int32", "sql.NullInt32", "Int32"),
+  ("int64", "sql.NullInt64", "Int64"),
+  ("float64", "sql.NullFloat64", "Float64"),
+  ("bool", "sql.NullBool", "Bool"),
+  ("time.Time", "sql.NullTime", "Time"),
+  ("decimal.Decimal", "decimal.NullDecimal", "Decimal"),
+];
+
+fn sql_null_type(value_type: &str) -> String {
+  match SQL_NULL_TYPES.iter().find(|(go, _, _)| *go == value_type) {
+    Some((_, null_type, _)) => null_type.to_string(),
+    None => format!("sql.Null[{}]", value_type),
+  }
+}
+
+// Maps and slices can already be nil, and GORM's soft delete timestamp
+// has its own way of being null
+fn is_nilable(value_type: &str) -> bool {
+  value_type.starts_with("map[") || value_type.starts_with("[]") || value_type == "gorm.DeletedAt"
+}
+
+// The column's type before nullability is taken into account
+fn base_type(column: &ColumnData, config: &Config) -> String {
+  let soft_delete = config.go.flavour == GoFlavour::Gorm
+    && column.name == "deleted_at"
+    && column.value_type == RailsColumn::Datetime;
+  let value_type = match column.numeric_type(config.adapter) {
+    _ if soft_delete => "gorm.DeletedAt".to_string(),
+    Some(numeric_type) => numeric_type.to_go(config),
+    None => column.value_type.to_go(config),
+  };
+  if column.options.array {
+    format!("[]{}", value_type)
+  } else {
+    value_type
+  }
+}
+
+fn value_type(column: &ColumnData, config: &Config) -> String {
+  let value_type = base_type(column, config);
+  if !column.nullable || is_nilable(&value_type) {
+    return value_type;
+  }
+  match config.go.nullable {
+    GoNullable::Pointer => format!("*{}", value_type),
+    GoNullable::SqlNull => sql_null_type(&value_type),
+    GoNullable::Value => value_type,
+  }
+}
+
+fn column_field(column: &ColumnData, table: Option<&Table>, config: &Config) -> [String; 3] {
+  field(column, &value_type(column, config), table, config)
+}
+
+// A column on its own doesn't know which indexes it's in
 impl ToGo for ColumnData {
-  fn to_go(&self) -> String {
-    format!("{}: {},", self.name, self.value_type.to_go())
+  fn to_go(&self, config: &Config) -> String {
+    column_field(self, None, config).join(" ")
   }
 }
 
 #[cfg(test)]
 pub mod test {
-  use crate::{go::ToGo, Database};
+  use crate::{
+    config::{Adapter, Config},
+    go::{GoDateTime, GoDecimal, GoFlavour, GoNullable, ToGo},
+    Database, Variant,
+  };
   #[test]
   fn can_convert_a_rails_schema_to_a_string_version_of_a_rust_struct() {
     let schema = "ActiveRecord::Schema.define(version: 20_210_916_202_951) do
@@ -93,25 +715,431 @@ pub mod test {
   end
 end";
     assert_eq!(
-      Database::from(schema).to_go(),
-      "type SampleSchema struct {
-    a: *int64,
-    b: *string,
-    c: *string,
-    d: *int64,
-    e: *int128,
-    f: *float64,
-    g: *float64,
-    h: *int64,
-    i: *time.Time,
-    j: *time.Time,
-    k: *time.Time,
-    l: *[]uint8,
-    m: *bool,
-    n: map[string]interface{},
-    o: map[string]interface{},
-    created_at: *time.Time,
-    updated_at: *time.Time,
+      Database::from(schema).to_go(&Config::default()),
+      "package models
+
+import (
+\t\"time\"
+
+\t\"github.com/shopspring/decimal\"
+)
+
+type SampleSchema struct {
+\tA         *int64                 `json:\"a\" db:\"a\"`
+\tB         *string                `json:\"b\" db:\"b\"`
+\tC         *string                `json:\"c\" db:\"c\"`
+\tD         *int32                 `json:\"d\" db:\"d\"`
+\tE         *int64                 `json:\"e\" db:\"e\"`
+\tF         *float64               `json:\"f\" db:\"f\"`
+\tG         *decimal.Decimal       `json:\"g\" db:\"g\"`
+\tH         *decimal.Decimal       `json:\"h\" db:\"h\"`
+\tI         *time.Time             `json:\"i\" db:\"i\"`
+\tJ         *time.Time             `json:\"j\" db:\"j\"`
+\tK         *time.Time             `json:\"k\" db:\"k\"`
+\tL         []byte                 `json:\"l\" db:\"l\"`
+\tM         *bool                  `json:\"m\" db:\"m\"`
+\tN         map[string]interface{} `json:\"n\" db:\"n\"`
+\tO         map[string]interface{} `json:\"o\" db:\"o\"`
+\tCreatedAt time.Time              `json:\"created_at\" db:\"created_at\"`
+\tUpdatedAt time.Time              `json:\"updated_at\" db:\"updated_at\"`
+}"
+    )
+  }
+
+  #[test]
+  fn integer_widths_follow_the_adapter() {
+    let schema = "create_table \"accounts\", id: false, force: :cascade do |t|
+    t.integer \"logins\"
+    t.integer \"visits\", limit: 2
+    t.decimal \"balance\", precision: 10, scale: 2
+  end";
+    let mut config = Config {
+      adapter: Adapter::Sqlite,
+      ..Config::default()
+    };
+    config.go.decimal = GoDecimal::String;
+    assert_eq!(
+      Database::from(schema).to_go(&config),
+      "package models
+
+type Account struct {
+\tLogins  *int64  `json:\"logins\" db:\"logins\"`
+\tVisits  *int16  `json:\"visits\" db:\"visits\"`
+\tBalance *string `json:\"balance\" db:\"balance\"`
+}"
+    )
+  }
+
+  #[test]
+  fn dates_and_times_can_use_civil() {
+    let schema = "create_table \"events\", id: false, force: :cascade do |t|
+    t.datetime \"starts_at\", null: false
+    t.date \"day\", null: false
+    t.time \"doors_open\", null: false
+  end";
+    let mut config = Config::default();
+    config.go.date_time = GoDateTime::Civil;
+    assert_eq!(
+      Database::from(schema).to_go(&config),
+      "package models
+
+import (
+\t\"time\"
+
+\t\"cloud.google.com/go/civil\"
+)
+
+type Event struct {
+\tStartsAt  time.Time  `json:\"starts_at\" db:\"starts_at\"`
+\tDay       civil.Date `json:\"day\" db:\"day\"`
+\tDoorsOpen civil.Time `json:\"doors_open\" db:\"doors_open\"`
+}"
+    )
+  }
+
+  #[test]
+  fn nullable_columns_can_use_sql_null_types() {
+    let schema = "create_table \"users\", id: false, force: :cascade do |t|
+    t.string \"name\", null: false
+    t.string \"nickname\"
+    t.integer \"age\"
+    t.date \"born_on\"
+    t.binary \"avatar\"
+  end";
+    let mut config = Config::default();
+    config.go.nullable = GoNullable::SqlNull;
+    config.go.date_time = GoDateTime::Civil;
+    assert_eq!(
+      Database::from(schema).to_go(&config),
+      "package models
+
+import (
+\t\"database/sql\"
+
+\t\"cloud.google.com/go/civil\"
+)
+
+type User struct {
+\tName     string               `json:\"name\" db:\"name\"`
+\tNickname sql.NullString       `json:\"nickname\" db:\"nickname\"`
+\tAge      sql.NullInt32        `json:\"age\" db:\"age\"`
+\tBornOn   sql.Null[civil.Date] `json:\"born_on\" db:\"born_on\"`
+\tAvatar   []byte               `json:\"avatar\" db:\"avatar\"`
+}"
+    )
+  }
+
+  #[test]
+  fn column_defaults_become_a_constructor() {
+    let schema = "create_table \"posts\", id: false, force: :cascade do |t|
+    t.string \"title\", null: false
+    t.string \"status\", default: \"draft\", null: false
+    t.integer \"views\", default: 0
+    t.datetime \"published_at\", default: -> { \"now()\" }, null: false
+  end";
+    let mut config = Config::default();
+    assert_eq!(
+      Database::from(schema).to_go(&config),
+      "package models
+
+import (
+\t\"time\"
+)
+
+type Post struct {
+\tTitle       string    `json:\"title\" db:\"title\"`
+\tStatus      string    `json:\"status\" db:\"status\"`
+\tViews       *int32    `json:\"views\" db:\"views\"`
+\tPublishedAt time.Time `json:\"published_at\" db:\"published_at\"`
+}
+
+func NewPost() Post {
+\treturn Post{
+\t\tStatus: \"draft\",
+\t\tViews:  ptr[int32](0),
+\t}
+}
+
+func ptr[T any](value T) *T {
+\treturn &value
+}"
+    );
+
+    config.go.nullable = GoNullable::SqlNull;
+    assert!(Database::from(schema)
+      .to_go(&config)
+      .contains("Views:  sql.NullInt32{Int32: 0, Valid: true},"));
+  }
+
+  #[test]
+  fn turning_variants_on_keeps_the_go_insert_name() {
+    let config: Config = "[go.variants]\nenabled = true".parse().unwrap();
+    assert!(config.go.variants.enabled);
+    assert_eq!(
+      config.go.variants.name(Variant::Insert, "User"),
+      "UserInsert"
+    );
+    assert_eq!(config.go.variants.name(Variant::Patch, "User"), "UserPatch");
+  }
+
+  #[test]
+  fn tables_can_have_insert_and_patch_variants() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+    t.string \"name\", null: false
+    t.string \"nickname\"
+    t.jsonb \"settings\", null: false
+  end";
+    let mut config = Config::default();
+    config.go.variants.enabled = true;
+    assert_eq!(
+      Database::from(schema).to_go(&config),
+      "package models
+
+type User struct {
+\tID       int64                  `json:\"id\" db:\"id\"`
+\tName     string                 `json:\"name\" db:\"name\"`
+\tNickname *string                `json:\"nickname\" db:\"nickname\"`
+\tSettings map[string]interface{} `json:\"settings\" db:\"settings\"`
+}
+
+type UserInsert struct {
+\tName     string                 `json:\"name\" db:\"name\"`
+\tNickname *string                `json:\"nickname,omitempty\" db:\"nickname\"`
+\tSettings map[string]interface{} `json:\"settings\" db:\"settings\"`
+}
+
+type UserPatch struct {
+\tName     *string                `json:\"name,omitempty\" db:\"name\"`
+\tNickname *string                `json:\"nickname,omitempty\" db:\"nickname\"`
+\tSettings map[string]interface{} `json:\"settings\" db:\"settings\"`
+}"
+    )
+  }
+
+  #[test]
+  fn reserved_words_and_invalid_identifiers_are_renamed() {
+    let schema = "create_table \"2fa_codes\", id: false, force: :cascade do |t|
+    t.string \"type\", null: false
+    t.string \"self\", null: false
+    t.integer \"range\", null: false
+    t.string \"123abc\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_go(&Config::default()),
+      "package models
+
+type X2faCode struct {
+\tType    string `json:\"type\" db:\"type\"`
+\tSelf    string `json:\"self\" db:\"self\"`
+\tRange   int32  `json:\"range\" db:\"range\"`
+\tX123abc string `json:\"123abc\" db:\"123abc\"`
+}"
+    )
+  }
+
+  #[test]
+  fn renamed_fields_keep_their_column_in_a_json_tag() {
+    let schema = "create_table \"users\", id: false, force: :cascade do |t|
+    t.string \"name\", null: false
+    t.string \"legacyCode\", null: false
+    t.datetime \"created_at\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_go(&Config::default()),
+      "package models
+
+import (
+\t\"time\"
+)
+
+type User struct {
+\tName       string    `json:\"name\" db:\"name\"`
+\tLegacyCode string    `json:\"legacyCode\" db:\"legacyCode\"`
+\tCreatedAt  time.Time `json:\"created_at\" db:\"created_at\"`
+}"
+    )
+  }
+
+  #[test]
+  fn fields_can_only_be_exported() {
+    assert!("[go]\nfield-case = \"camel\"".parse::<Config>().is_err())
+  }
+
+  #[test]
+  fn fields_are_exported_with_go_initialisms_in_the_configured_package() {
+    let schema = "create_table \"api_keys\", force: :cascade do |t|
+    t.bigint \"user_id\", null: false
+    t.string \"callback_url\"
+    t.datetime \"created_at\", null: false
+  end";
+    let mut config = Config::default();
+    config.go.package = "db".to_string();
+    assert_eq!(
+      Database::from(schema).to_go(&config),
+      "package db
+
+import (
+\t\"time\"
+)
+
+type APIKey struct {
+\tID          int64     `json:\"id\" db:\"id\"`
+\tUserID      int64     `json:\"user_id\" db:\"user_id\"`
+\tCallbackURL *string   `json:\"callback_url\" db:\"callback_url\"`
+\tCreatedAt   time.Time `json:\"created_at\" db:\"created_at\"`
+}"
+    )
+  }
+
+  #[test]
+  fn the_gorm_flavour_tags_fields_for_gorm_and_adds_associations() {
+    let schema = "create_enum \"mood\", [\"happy\", \"sad\"]
+  create_table \"users\", force: :cascade do |t|
+    t.string \"email\", limit: 255, null: false
+    t.enum \"mood\", enum_type: \"mood\", default: \"happy\", null: false
+    t.jsonb \"settings\"
+    t.datetime \"deleted_at\"
+    t.index [\"email\"], name: \"index_users_on_email\", unique: true
+  end
+  create_table \"blog_posts\", id: :uuid, default: -> { \"gen_random_uuid()\" }, force: :cascade do |t|
+    t.bigint \"author_id\", null: false
+    t.string \"title\", null: false
+    t.index [\"author_id\", \"title\"], name: \"index_blog_posts_on_author_id_and_title\"
+  end
+  create_table \"person\", force: :cascade do |t|
+  end
+  add_foreign_key \"blog_posts\", \"users\", column: \"author_id\"";
+    let mut config = Config::default();
+    config.go.flavour = GoFlavour::Gorm;
+    config.go.defaults = false;
+    assert_eq!(
+      Database::from(schema).to_go(&config),
+      "package models
+
+import (
+\t\"gorm.io/gorm\"
+)
+
+type User struct {
+\tID        int64                  `json:\"id\" gorm:\"column:id;primaryKey\"`
+\tEmail     string                 `json:\"email\" gorm:\"column:email;type:varchar(255);not null;uniqueIndex:index_users_on_email\"`
+\tMood      string                 `json:\"mood\" gorm:\"column:mood;type:mood;not null;default:'happy'\"`
+\tSettings  map[string]interface{} `json:\"settings\" gorm:\"column:settings;type:jsonb;serializer:json\"`
+\tDeletedAt gorm.DeletedAt         `json:\"deleted_at\" gorm:\"column:deleted_at\"`
+\tBlogPosts []BlogPost             `json:\"blog_posts,omitempty\" gorm:\"foreignKey:AuthorID\"`
+}
+
+type BlogPost struct {
+\tID       string `json:\"id\" gorm:\"column:id;type:uuid;primaryKey;default:gen_random_uuid()\"`
+\tAuthorID int64  `json:\"author_id\" gorm:\"column:author_id;not null;index:index_blog_posts_on_author_id_and_title,priority:1\"`
+\tTitle    string `json:\"title\" gorm:\"column:title;not null;index:index_blog_posts_on_author_id_and_title,priority:2\"`
+\tAuthor   *User  `json:\"author,omitempty\" gorm:\"foreignKey:AuthorID\"`
+}
+
+type Person struct {
+\tID int64 `json:\"id\" gorm:\"column:id;primaryKey\"`
+}
+
+func (Person) TableName() string {
+\treturn \"person\"
+}"
+    )
+  }
+
+  #[test]
+  fn the_output_is_valid_go() {
+    let schema = "create_table \"2fa_codes\", force: :cascade do |t|
+    t.string \"type\", default: \"totp\", null: false
+    t.string \"123abc\"
+    t.integer \"count\", limit: 2, default: 0
+    t.float \"ratio\", default: 0.5
+    t.decimal \"price\", precision: 10, scale: 2, default: \"9.99\"
+    t.boolean \"active\", default: true
+    t.datetime \"created_at\", default: -> { \"now()\" }, null: false
+    t.date \"day\"
+    t.time \"at\"
+    t.binary \"data\"
+    t.hstore \"tags\", default: {}
+    t.jsonb \"settings\"
+    t.uuid \"token\"
+    t.string \"labels\", array: true, default: [], null: false
+    t.bigint \"parent_id\"
+    t.datetime \"deleted_at\"
+    t.index [\"type\", \"count\"]
+  end
+  create_table \"users\", id: false, force: :cascade do |t|
+  end
+  add_foreign_key \"2fa_codes\", \"2fa_codes\", column: \"parent_id\"";
+    let mut config = Config::default();
+    config.go.variants.enabled = true;
+    let mut parser = tree_sitter::Parser::new();
+    parser
+      .set_language(&tree_sitter_go::LANGUAGE.into())
+      .expect("the Go grammar loads");
+    for nullable in [GoNullable::Pointer, GoNullable::SqlNull, GoNullable::Value] {
+      for date_time in [GoDateTime::Time, GoDateTime::Civil] {
+        for flavour in [GoFlavour::Plain, GoFlavour::Gorm] {
+          config.go.nullable = nullable;
+          config.go.date_time = date_time;
+          config.go.flavour = flavour;
+          let go = Database::from(schema).to_go(&config);
+          // Go ends the last declaration at the end of the file, the grammar wants a newline
+          let go = format!("{}\n", go);
+          let tree = parser.parse(&go, None).expect("the parser returns a tree");
+          if tree.root_node().has_error() {
+            panic!("{}\n\n{}", tree.root_node().to_sexp(), go);
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn numeric_defaults_take_the_column_type() {
+    let schema = "create_table \"accounts\", id: false, force: :cascade do |t|
+    t.decimal \"balance\", precision: 10, scale: 2, default: \"0.0\", null: false
+    t.float \"ratio\", default: 0, null: false
+    t.decimal \"amount\", precision: 9, scale: 0, default: \"5\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_go(&Config::default()),
+      "package models
+
+import (
+\t\"github.com/shopspring/decimal\"
+)
+
+type Account struct {
+\tBalance decimal.Decimal `json:\"balance\" db:\"balance\"`
+\tRatio   float64         `json:\"ratio\" db:\"ratio\"`
+\tAmount  int32           `json:\"amount\" db:\"amount\"`
+}
+
+func NewAccount() Account {
+\treturn Account{
+\t\tBalance: decimal.RequireFromString(\"0.0\"),
+\t\tRatio:   0,
+\t\tAmount:  5,
+\t}
+}"
+    )
+  }
+
+  #[test]
+  fn tables_whose_defaults_are_all_expressions_get_no_constructor() {
+    let schema = "create_table \"categories\", id: false, force: :cascade do |t|
+    t.datetime \"created_at\", default: -> { \"now()\" }, null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_go(&Config::default()),
+      "package models
+
+import (
+\t\"time\"
+)
+
+type Category struct {
+\tCreatedAt time.Time `json:\"created_at\" db:\"created_at\"`
 }"
     )
   }
@@ -0,0 +1,348 @@
+// Turns Rails table names into their singular form and back, using the same
+// rules as ActiveSupport's inflector so the names match the app's models.
+
+use std::{collections::BTreeMap, sync::LazyLock};
+
+use regex::Regex;
+use serde::Deserialize;
+
+// ActiveSupport's English rules, written the way Rails writes them. Later
+// rules take precedence over earlier ones.
+const PLURALS: &[(&str, &str)] = &[
+  (r"$", r"s"),
+  (r"s$", r"s"),
+  (r"^(ax|test)is$", r"\1es"),
+  (r"(octop|vir)us$", r"\1i"),
+  (r"(octop|vir)i$", r"\1i"),
+  (r"(alias|status)$", r"\1es"),
+  (r"(bu)s$", r"\1ses"),
+  (r"(buffal|tomat)o$", r"\1oes"),
+  (r"([ti])um$", r"\1a"),
+  (r"([ti])a$", r"\1a"),
+  (r"sis$", r"ses"),
+  (r"(?:([^f])fe|([lr])f)$", r"\1\2ves"),
+  (r"(hive)$", r"\1s"),
+  (r"([^aeiouy]|qu)y$", r"\1ies"),
+  (r"(x|ch|ss|sh)$", r"\1es"),
+  (r"(matr|vert|ind)(?:ix|ex)$", r"\1ices"),
+  (r"^(m|l)ouse$", r"\1ice"),
+  (r"^(m|l)ice$", r"\1ice"),
+  (r"^(ox)$", r"\1en"),
+  (r"^(oxen)$", r"\1"),
+  (r"(quiz)$", r"\1zes"),
+];
+
+const SINGULARS: &[(&str, &str)] = &[
+  (r"s$", r""),
+  (r"(ss)$", r"\1"),
+  (r"(n)ews$", r"\1ews"),
+  (r"([ti])a$", r"\1um"),
+  (
+    r"((a)naly|(b)a|(d)iagno|(p)arenthe|(p)rogno|(s)ynop|(t)he)(sis|ses)$",
+    r"\1sis",
+  ),
+  (r"(^analy)(sis|ses)$", r"\1sis"),
+  (r"([^f])ves$", r"\1fe"),
+  (r"(hive)s$", r"\1"),
+  (r"(tive)s$", r"\1"),
+  (r"([lr])ves$", r"\1f"),
+  (r"([^aeiouy]|qu)ies$", r"\1y"),
+  (r"(s)eries$", r"\1eries"),
+  (r"(m)ovies$", r"\1ovie"),
+  (r"(x|ch|ss|sh)es$", r"\1"),
+  (r"^(m|l)ice$", r"\1ouse"),
+  (r"(bus)(es)?$", r"\1"),
+  (r"(o)es$", r"\1"),
+  (r"(shoe)s$", r"\1"),
+  (r"(cris|test)(is|es)$", r"\1is"),
+  (r"^(a)x[ie]s$", r"\1xis"),
+  (r"(octop|vir)(us|i)$", r"\1us"),
+  (r"(alias|status)(es)?$", r"\1"),
+  (r"^(ox)en", r"\1"),
+  (r"(vert|ind)ices$", r"\1ex"),
+  (r"(matr)ices$", r"\1ix"),
+  (r"(quiz)zes$", r"\1"),
+  (r"(database)s$", r"\1"),
+];
+
+// Like Rails, these also apply to the end of a compound word, so
+// `salespeople` becomes `salesperson`
+const IRREGULARS: &[(&str, &str)] = &[
+  ("person", "people"),
+  ("man", "men"),
+  ("child", "children"),
+  ("sex", "sexes"),
+  ("move", "moves"),
+  ("zombie", "zombies"),
+];
+
+const UNCOUNTABLES: &[&str] = &[
+  "equipment",
+  "information",
+  "rice",
+  "money",
+  "species",
+  "series",
+  "fish",
+  "sheep",
+  "jeans",
+  "police",
+];
+
+// Extra rules for words the defaults get wrong, the same as you'd give
+// `ActiveSupport::Inflector.inflections` in an initializer
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct InflectionConfig {
+  // Pairs of a pattern and its replacement, e.g. ["^(ox)$", "\\1en"]
+  pub plural: Vec<(String, String)>,
+  pub singular: Vec<(String, String)>,
+  // Maps a singular word to its plural
+  pub irregular: BTreeMap<String, String>,
+  pub uncountable: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+  pattern: Regex,
+  replacement: String,
+}
+
+impl Rule {
+  // Rules match regardless of case and use Ruby's `\1` for capture groups
+  fn new(pattern: &str, replacement: &str) -> Result<Self, String> {
+    let pattern = Regex::new(&format!("(?i){}", pattern))
+      .map_err(|why| format!("invalid inflection {}: {}", pattern, why))?;
+    let mut rust_replacement = String::new();
+    let mut characters = replacement.chars().peekable();
+    while let Some(character) = characters.next() {
+      match (character, characters.peek()) {
+        ('\\', Some(group)) if group.is_ascii_digit() => {
+          rust_replacement.push_str(&format!("${{{}}}", group));
+          characters.next();
+        }
+        ('$', _) => rust_replacement.push_str("$$"),
+        (character, _) => rust_replacement.push(character),
+      }
+    }
+    Ok(Rule {
+      pattern,
+      replacement: rust_replacement,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "InflectionConfig")]
+pub struct Inflections {
+  plurals: Vec<Rule>,
+  singulars: Vec<Rule>,
+  uncountables: Vec<Regex>,
+}
+
+// Compiling the rules isn't free, so the defaults are only built once
+static ENGLISH: LazyLock<Inflections> = LazyLock::new(|| {
+  Inflections::try_from(InflectionConfig::default()).expect("the default inflections are valid")
+});
+
+impl Default for Inflections {
+  fn default() -> Self {
+    ENGLISH.clone()
+  }
+}
+
+impl TryFrom<InflectionConfig> for Inflections {
+  type Error = String;
+
+  fn try_from(config: InflectionConfig) -> Result<Self, Self::Error> {
+    let rules = |defaults: &[(&str, &str)], custom: &[(String, String)]| {
+      defaults
+        .iter()
+        .map(|(pattern, replacement)| Rule::new(pattern, replacement))
+        .chain(
+          custom
+            .iter()
+            .map(|(pattern, replacement)| Rule::new(pattern, replacement)),
+        )
+        .collect::<Result<Vec<Rule>, String>>()
+    };
+    let mut inflections = Inflections {
+      plurals: rules(PLURALS, &config.plural)?,
+      singulars: rules(SINGULARS, &config.singular)?,
+      uncountables: vec![],
+    };
+
+    let irregulars = IRREGULARS
+      .iter()
+      .map(|(singular, plural)| (singular.to_string(), plural.to_string()))
+      .chain(config.irregular);
+    let mut irregular_words = vec![];
+    for (singular, plural) in irregulars {
+      inflections.irregular(&singular, &plural)?;
+      irregular_words.extend([singular, plural]);
+    }
+
+    // A word made irregular by the config stops being uncountable
+    let uncountables = UNCOUNTABLES
+      .iter()
+      .map(|word| word.to_string())
+      .chain(config.uncountable)
+      .filter(|word| !irregular_words.contains(word));
+    for word in uncountables {
+      let pattern = Regex::new(&format!(r"(?i)\b{}$", regex::escape(&word)))
+        .map_err(|why| format!("invalid uncountable {}: {}", word, why))?;
+      inflections.uncountables.push(pattern);
+    }
+    Ok(inflections)
+  }
+}
+
+impl Inflections {
+  // Mirrors `inflect.irregular`, keeping the case of the word's first letter
+  // when both forms share it
+  fn irregular(&mut self, singular: &str, plural: &str) -> Result<(), String> {
+    let head = |word: &str| word.chars().next().map_or(0, char::len_utf8);
+    let (singular_head, singular_rest) = singular.split_at(head(singular));
+    let (plural_head, plural_rest) = plural.split_at(head(plural));
+    let shared_head = singular_head.eq_ignore_ascii_case(plural_head);
+    for word in [singular, plural] {
+      let (pattern, plural, singular) = if shared_head {
+        let (head, rest) = word.split_at(head(word));
+        (
+          format!("({}){}$", regex::escape(head), regex::escape(rest)),
+          format!(r"\1{}", plural_rest),
+          format!(r"\1{}", singular_rest),
+        )
+      } else {
+        (
+          format!("{}$", regex::escape(word)),
+          plural.to_string(),
+          singular.to_string(),
+        )
+      };
+      self.plurals.push(Rule::new(&pattern, &plural)?);
+      self.singulars.push(Rule::new(&pattern, &singular)?);
+    }
+    Ok(())
+  }
+
+  // Applies the most recently defined rule that matches
+  fn apply(&self, word: &str, rules: &[Rule]) -> String {
+    if word.is_empty()
+      || self
+        .uncountables
+        .iter()
+        .any(|pattern| pattern.is_match(word))
+    {
+      return word.to_string();
+    }
+    rules
+      .iter()
+      .rev()
+      .find(|rule| rule.pattern.is_match(word))
+      .map_or_else(
+        || word.to_string(),
+        |rule| {
+          rule
+            .pattern
+            .replace(word, rule.replacement.as_str())
+            .into_owned()
+        },
+      )
+  }
+
+  pub fn pluralize(&self, word: &str) -> String {
+    self.apply(word, &self.plurals)
+  }
+
+  pub fn singularize(&self, word: &str) -> String {
+    self.apply(word, &self.singulars)
+  }
+}
+
+// Pluralizes with the default rules, for when there's no config to hand
+pub fn pluralize(word: &str) -> String {
+  ENGLISH.pluralize(word)
+}
+
+pub fn singularize(word: &str) -> String {
+  ENGLISH.singularize(word)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{pluralize, singularize, Inflections};
+
+  #[test]
+  fn it_follows_the_regular_suffix_rules() {
+    assert_eq!(pluralize("user"), "users");
+    assert_eq!(pluralize("category"), "categories");
+    assert_eq!(pluralize("day"), "days");
+    assert_eq!(pluralize("address"), "addresses");
+    assert_eq!(pluralize("branch"), "branches");
+    assert_eq!(singularize("users"), "user");
+    assert_eq!(singularize("categories"), "category");
+    assert_eq!(singularize("addresses"), "address");
+    assert_eq!(singularize("branches"), "branch");
+    assert_eq!(singularize("status"), "status");
+  }
+
+  #[test]
+  fn it_knows_the_irregular_plurals() {
+    assert_eq!(pluralize("person"), "people");
+    assert_eq!(pluralize("salesperson"), "salespeople");
+    assert_eq!(pluralize("people"), "people");
+    assert_eq!(singularize("children"), "child");
+    assert_eq!(singularize("account_men"), "account_man");
+  }
+
+  #[test]
+  fn it_matches_active_support() {
+    let words = [
+      ("analysis", "analyses"),
+      ("wife", "wives"),
+      ("half", "halves"),
+      ("matrix", "matrices"),
+      ("index", "indices"),
+      ("mouse", "mice"),
+      ("ox", "oxen"),
+      ("quiz", "quizzes"),
+      ("octopus", "octopi"),
+      ("bus", "buses"),
+      ("tomato", "tomatoes"),
+      ("medium", "media"),
+      ("movie", "movies"),
+      ("database", "databases"),
+      ("line_item", "line_items"),
+    ];
+    for (singular, plural) in words {
+      assert_eq!(pluralize(singular), plural);
+      assert_eq!(singularize(plural), singular);
+    }
+  }
+
+  #[test]
+  fn uncountable_words_stay_the_same() {
+    assert_eq!(pluralize("equipment"), "equipment");
+    assert_eq!(singularize("series"), "series");
+    assert_eq!(singularize("news"), "news");
+    // Rails only checks whole words, and an underscore doesn't end one
+    assert_eq!(pluralize("office_equipment"), "office_equipments");
+  }
+
+  #[test]
+  fn custom_rules_take_precedence_over_the_defaults() {
+    let inflections: Inflections = toml::from_str(
+      "plural = [[\"^(ox)$\", \"\\\\1es\"]]
+uncountable = [\"metadata\"]
+
+[irregular]
+cactus = \"cacti\"",
+    )
+    .unwrap();
+    assert_eq!(inflections.pluralize("ox"), "oxes");
+    assert_eq!(inflections.singularize("metadata"), "metadata");
+    assert_eq!(inflections.singularize("cacti"), "cactus");
+    assert_eq!(inflections.pluralize("cactus"), "cacti");
+  }
+}
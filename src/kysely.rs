@@ -0,0 +1,383 @@
+use super::case::Case;
+use super::config::{Adapter, Config};
+use super::typescript::key;
+use super::ColumnData;
+use super::Database;
+use super::EnumType;
+use super::NumericType;
+use super::RailsColumn;
+use super::Table;
+
+// Column types for values the database driver hands back differently from
+// how it accepts them, like Postgres `int8` which node-postgres reads as a string
+const COLUMN_TYPES: &[(&str, &str)] = &[
+  (
+    "Int8",
+    "export type Int8 = ColumnType<string, bigint | number | string, bigint | number | string>;",
+  ),
+  (
+    "Numeric",
+    "export type Numeric = ColumnType<string, number | string, number | string>;",
+  ),
+  (
+    "Timestamp",
+    "export type Timestamp = ColumnType<Date, Date | string, Date | string>;",
+  ),
+];
+
+// Kysely's own `Generated` can't wrap a `ColumnType`, so this one makes
+// either kind optional on insert
+const GENERATED: &str = "export type Generated<T> = T extends ColumnType<infer S, infer I, infer U>
+  ? ColumnType<S, I | undefined, U>
+  : ColumnType<T, T | undefined, T>;";
+
+pub trait ToKysely {
+  fn to_kysely(&self, config: &Config) -> String;
+}
+
+// Tables are keyed by their name in the `Database` interface, which is what
+// `new Kysely<Database>()` takes
+impl ToKysely for Database {
+  fn to_kysely(&self, config: &Config) -> String {
+    let tables = self
+      .0
+      .iter()
+      .map(|table| table.to_kysely(config))
+      .collect::<Vec<String>>();
+    let database = self.0.iter().fold(String::new(), |fields, table| {
+      [
+        fields,
+        format!(
+          "  {}: {}Table;",
+          key(table.name.clone()),
+          table.type_name(config)
+        ),
+      ]
+      .join("\n")
+    });
+    let database = format!("export interface Database {{{}\n}}", database);
+    let body = tables.join("\n\n");
+
+    let mut aliases = vec![];
+    if uses(&body, "Generated") {
+      aliases.push(GENERATED.to_string());
+    }
+    aliases.extend(
+      COLUMN_TYPES
+        .iter()
+        .filter(|(name, _)| uses(&body, name))
+        .map(|(_, declaration)| declaration.to_string()),
+    );
+    let used = [body.clone(), aliases.join("\n")].join("\n");
+    let imports = ["ColumnType", "JSONColumnType"]
+      .into_iter()
+      .filter(|name| uses(&used, name))
+      .collect::<Vec<&str>>();
+    let mut declarations = vec![];
+    if !imports.is_empty() {
+      declarations.push(format!(
+        "import type {{ {} }} from \"kysely\";",
+        imports.join(", ")
+      ));
+    }
+    declarations.extend(aliases);
+    declarations.extend(self.enums().into_iter().filter_map(|enum_type| {
+      if enum_type.values.is_empty() {
+        return None;
+      }
+      Some(format!(
+        "export type {} = {};",
+        enum_name(enum_type, config),
+        union(&enum_type.values)
+      ))
+    }));
+
+    [declarations, tables, vec![database]]
+      .concat()
+      .into_iter()
+      .filter(|section| !section.is_empty())
+      .collect::<Vec<String>>()
+      .join("\n\n")
+  }
+}
+
+// Whether a type name appears in the body as a whole word
+fn uses(body: &str, name: &str) -> bool {
+  body
+    .split(|c: char| !c.is_alphanumeric() && c != '_')
+    .any(|word| word == name)
+}
+
+fn enum_name(enum_type: &EnumType, config: &Config) -> String {
+  let name = Case::Pascal.convert(&enum_type.name, &config.acronyms);
+  if name.starts_with(|first: char| first.is_ascii_digit()) {
+    format!("_{}", name)
+  } else {
+    name
+  }
+}
+
+fn union(values: &[String]) -> String {
+  values
+    .iter()
+    .map(|value| format!("{:?}", value))
+    .collect::<Vec<String>>()
+    .join(" | ")
+}
+
+// A row interface named after the table's type, keyed by the column names
+// as they are, since those are what Kysely puts in its queries
+impl ToKysely for Table {
+  fn to_kysely(&self, config: &Config) -> String {
+    let fields = self.columns.iter().fold(String::new(), |fields, column| {
+      [
+        fields,
+        format!(
+          "  {}: {};",
+          key(column.name.clone()),
+          column.to_kysely(config)
+        ),
+      ]
+      .join("\n")
+    });
+    format!(
+      "export interface {}Table {{{}\n}}",
+      self.type_name(config),
+      fields
+    )
+  }
+}
+
+// The type the driver reads a single value as, or a `ColumnType` alias
+// when it accepts more than it returns
+fn value_type(column: &ColumnData, config: &Config) -> String {
+  let adapter = config.adapter;
+  match &column.options.enum_type {
+    Some(enum_type) if !enum_type.values.is_empty() => return enum_name(enum_type, config),
+    _ if !column.options.allowed_values.is_empty() => {
+      return union(&column.options.allowed_values);
+    }
+    _ => {}
+  }
+  match (&column.value_type, adapter) {
+    // Every driver reads decimals as strings, except SQLite which has none
+    (RailsColumn::Decimal | RailsColumn::Numeric, Adapter::Sqlite) => "number".to_string(),
+    (RailsColumn::Decimal | RailsColumn::Numeric, _) => "Numeric".to_string(),
+    (RailsColumn::Datetime | RailsColumn::Date, Adapter::Sqlite) => "string".to_string(),
+    (RailsColumn::Datetime | RailsColumn::Date, _) => "Timestamp".to_string(),
+    // MySQL and SQLite store booleans as integers
+    (RailsColumn::Boolean, Adapter::Postgresql) => "boolean".to_string(),
+    (RailsColumn::Boolean, _) => "number".to_string(),
+    (RailsColumn::JsonB, Adapter::Sqlite) => "string".to_string(),
+    (RailsColumn::JsonB, _) => "JSONColumnType<any>".to_string(),
+    (RailsColumn::Binary, _) => "Buffer".to_string(),
+    _ => match column.numeric_type(adapter) {
+      Some(NumericType::BigInt) if adapter == Adapter::Postgresql => "Int8".to_string(),
+      Some(_) => "number".to_string(),
+      None => "string".to_string(),
+    },
+  }
+}
+
+// The plain type an alias selects as, for array elements where a
+// `ColumnType` can't go
+fn select_type(value_type: String) -> String {
+  match value_type.as_str() {
+    "Int8" | "Numeric" => "string".to_string(),
+    "Timestamp" => "Date".to_string(),
+    "JSONColumnType<any>" => "any".to_string(),
+    _ => value_type,
+  }
+}
+
+impl ToKysely for ColumnData {
+  fn to_kysely(&self, config: &Config) -> String {
+    let value_type = value_type(self, config);
+    let value_type = if self.options.array {
+      match select_type(value_type) {
+        value_type if value_type.contains(' ') => format!("({})[]", value_type),
+        value_type => format!("{}[]", value_type),
+      }
+    } else {
+      value_type
+    };
+    let value_type = if self.nullable {
+      format!("{} | null", value_type)
+    } else {
+      value_type
+    };
+    // Columns the database fills in can be left out of an insert
+    if self.is_generated_primary_key() || self.options.default.is_some() {
+      format!("Generated<{}>", value_type)
+    } else {
+      value_type
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    case::Case,
+    config::{Adapter, Config},
+    kysely::ToKysely,
+    Database,
+  };
+
+  #[test]
+  fn tables_become_row_interfaces_in_a_database_interface() {
+    let schema = "create_enum \"mood\", [\"happy\", \"sad\"]
+  create_table \"users\", force: :cascade do |t|
+    t.string \"name\", limit: 100, null: false
+    t.string \"role\", default: \"member\", null: false
+    t.enum \"mood\", enum_type: \"mood\"
+    t.string \"tags\", array: true, null: false
+    t.integer \"logins\", default: 0, null: false
+    t.decimal \"balance\", precision: 10, scale: 2
+    t.boolean \"admin\", default: false, null: false
+    t.jsonb \"settings\"
+    t.datetime \"created_at\", null: false
+    t.check_constraint \"role IN ('member', 'admin')\", name: \"role_check\"
+  end
+  create_table \"posts\", id: :uuid, default: -> { \"gen_random_uuid()\" }, force: :cascade do |t|
+    t.bigint \"user_id\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_kysely(&Config::default()),
+      "import type { ColumnType, JSONColumnType } from \"kysely\";
+
+export type Generated<T> = T extends ColumnType<infer S, infer I, infer U>
+  ? ColumnType<S, I | undefined, U>
+  : ColumnType<T, T | undefined, T>;
+
+export type Int8 = ColumnType<string, bigint | number | string, bigint | number | string>;
+
+export type Numeric = ColumnType<string, number | string, number | string>;
+
+export type Timestamp = ColumnType<Date, Date | string, Date | string>;
+
+export type Mood = \"happy\" | \"sad\";
+
+export interface UserTable {
+  id: Generated<Int8>;
+  name: string;
+  role: Generated<\"member\" | \"admin\">;
+  mood: Mood | null;
+  tags: string[];
+  logins: Generated<number>;
+  balance: Numeric | null;
+  admin: Generated<boolean>;
+  settings: JSONColumnType<any> | null;
+  created_at: Timestamp;
+}
+
+export interface PostTable {
+  id: Generated<string>;
+  user_id: Int8;
+}
+
+export interface Database {
+  users: UserTable;
+  posts: PostTable;
+}"
+    );
+  }
+
+  #[test]
+  fn types_follow_what_the_adapter_reads() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+    t.decimal \"balance\", precision: 10, scale: 2, null: false
+    t.boolean \"admin\", null: false
+    t.json \"settings\"
+    t.datetime \"created_at\", null: false
+  end";
+    let mut config = Config {
+      adapter: Adapter::Mysql,
+      ..Config::default()
+    };
+    let mysql = Database::from(schema).to_kysely(&config);
+    config.adapter = Adapter::Sqlite;
+    let sqlite = Database::from(schema).to_kysely(&config);
+    assert_eq!(
+      [mysql, sqlite].join("\n\n"),
+      "import type { ColumnType, JSONColumnType } from \"kysely\";
+
+export type Generated<T> = T extends ColumnType<infer S, infer I, infer U>
+  ? ColumnType<S, I | undefined, U>
+  : ColumnType<T, T | undefined, T>;
+
+export type Numeric = ColumnType<string, number | string, number | string>;
+
+export type Timestamp = ColumnType<Date, Date | string, Date | string>;
+
+export interface UserTable {
+  id: Generated<number>;
+  balance: Numeric;
+  admin: number;
+  settings: JSONColumnType<any> | null;
+  created_at: Timestamp;
+}
+
+export interface Database {
+  users: UserTable;
+}
+
+import type { ColumnType } from \"kysely\";
+
+export type Generated<T> = T extends ColumnType<infer S, infer I, infer U>
+  ? ColumnType<S, I | undefined, U>
+  : ColumnType<T, T | undefined, T>;
+
+export interface UserTable {
+  id: Generated<number>;
+  balance: number;
+  admin: number;
+  settings: string | null;
+  created_at: string;
+}
+
+export interface Database {
+  users: UserTable;
+}"
+    );
+  }
+
+  #[test]
+  fn keys_are_the_column_names_whatever_the_field_case() {
+    let schema = "create_table \"users\", id: false, force: :cascade do |t|
+    t.string \"first_name\", null: false
+  end";
+    let mut config = Config::default();
+    config.typescript.field_case = Case::Camel;
+    assert!(Database::from(schema)
+      .to_kysely(&config)
+      .contains("export interface UserTable {\n  first_name: string;\n}"))
+  }
+
+  #[test]
+  fn the_output_is_valid_typescript() {
+    let schema = "create_enum \"status\", [\"draft\", \"published\"]
+  create_table \"2fa_codes\", force: :cascade do |t|
+    t.enum \"status\", enum_type: \"status\", array: true, null: false
+    t.string \"labels\", array: true
+    t.hstore \"settings\"
+    t.string \"default\", limit: 10
+    t.string \"123abc\"
+    t.decimal \"balance\", precision: 10, scale: 2, array: true
+    t.binary \"data\"
+    t.time \"at\"
+    t.date \"on\"
+    t.datetime \"created_at\", default: -> { \"now()\" }, null: false
+  end";
+    let mut config = Config::default();
+    for adapter in [Adapter::Postgresql, Adapter::Mysql, Adapter::Sqlite] {
+      config.adapter = adapter;
+      let kysely = Database::from(schema).to_kysely(&config);
+      let allocator = oxc_allocator::Allocator::default();
+      let parsed = oxc_parser::Parser::new(&allocator, &kysely, oxc_span::SourceType::ts()).parse();
+      if let Some(error) = parsed.errors.first() {
+        panic!("{}\n\n{}", error, kysely);
+      }
+    }
+  }
+}
@@ -1,14 +1,82 @@
+pub mod case;
+pub mod config;
+pub mod diesel;
+pub mod drizzle;
+pub mod inflector;
+pub mod kysely;
+pub mod prisma;
 pub mod rails_parser;
+pub mod relationships;
 pub mod rust;
+pub mod sea_orm;
 pub mod spec;
+pub mod typeorm;
 pub mod typescript;
 pub mod go;
+pub mod zod;
+
+use case::Case;
+use config::{Adapter, Config, TypeNames};
 
 #[derive(Debug, PartialEq)]
 pub struct ColumnData {
   value_type: RailsColumn,
   name: String,
   nullable: bool,
+  options: ColumnOptions,
+}
+
+// The options hash that trails a column declaration, e.g. `limit: 8`
+#[derive(Debug, Default, PartialEq)]
+pub struct ColumnOptions {
+  limit: Option<u32>,
+  precision: Option<u32>,
+  scale: Option<u32>,
+  default: Option<DefaultValue>,
+  // Set on the `id` column Rails adds to every table unless told otherwise
+  primary_key: bool,
+  foreign_key: Option<ForeignKey>,
+  // Postgres array columns, declared with `array: true`
+  array: bool,
+  // The type behind a `t.enum` column
+  enum_type: Option<EnumType>,
+  // The only values a check constraint allows, e.g. `status IN ('draft', 'published')`
+  allowed_values: Vec<String>,
+}
+
+// A Postgres enum, from a `create_enum "mood", ["happy", "sad"]` declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumType {
+  pub name: String,
+  pub values: Vec<String>,
+}
+
+// The row a column points at, from an `add_foreign_key` declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForeignKey {
+  table: String,
+  primary_key: String,
+}
+
+// An index over one or more columns, from `t.index` or `add_index`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Index {
+  name: Option<String>,
+  columns: Vec<String>,
+  unique: bool,
+}
+
+// A column's `default:`. Literals can be reproduced by the generators, while
+// expressions like `-> { "now()" }` are only known to the database.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefaultValue {
+  Boolean(bool),
+  Integer(i64),
+  Float(f64),
+  String(String),
+  Array(Vec<DefaultValue>),
+  Hash(Vec<(String, DefaultValue)>),
+  Expression(String),
 }
 
 // An enum contains it's type and key value
@@ -29,12 +97,193 @@ pub enum RailsColumn {
   HStore,
   JsonB,
   Boolean,
+  Uuid,
+  Enum,
+}
+
+// The companion types generated alongside a table for writing to it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Variant {
+  // A new row, without anything the database fills in
+  Insert,
+  // A partial update where every column can be left out
+  Patch,
+}
+
+// What a numeric column is stored as once its options and the adapter's
+// defaults have been taken into account
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumericType {
+  SmallInt,
+  Int,
+  BigInt,
+  Float,
+  Decimal,
+}
+
+impl NumericType {
+  // Picks the smallest integer that can hold `bytes` worth of data
+  fn from_limit(bytes: u32) -> Self {
+    match bytes {
+      0..=2 => NumericType::SmallInt,
+      3..=4 => NumericType::Int,
+      _ => NumericType::BigInt,
+    }
+  }
+
+  // Picks the smallest integer that can hold `digits` decimal digits, if there is one
+  fn from_precision(digits: u32) -> Option<Self> {
+    match digits {
+      0..=4 => Some(NumericType::SmallInt),
+      5..=9 => Some(NumericType::Int),
+      10..=18 => Some(NumericType::BigInt),
+      _ => None,
+    }
+  }
+}
+
+impl RailsColumn {
+  pub fn numeric_type(&self, adapter: Adapter) -> Option<NumericType> {
+    match self {
+      RailsColumn::PrimaryKey | RailsColumn::Bigint => Some(NumericType::BigInt),
+      // SQLite stores every integer in 8 bytes
+      RailsColumn::Integer if adapter == Adapter::Sqlite => Some(NumericType::BigInt),
+      RailsColumn::Integer => Some(NumericType::Int),
+      RailsColumn::Float => Some(NumericType::Float),
+      RailsColumn::Decimal | RailsColumn::Numeric => Some(NumericType::Decimal),
+      _ => None,
+    }
+  }
+}
+
+impl DefaultValue {
+  // Renders the default as the equivalent JSON literal, if it has one
+  pub fn to_json(&self) -> Option<String> {
+    match self {
+      DefaultValue::Boolean(boolean) => Some(boolean.to_string()),
+      DefaultValue::Integer(integer) => Some(integer.to_string()),
+      DefaultValue::Float(float) => Some(float.to_string()),
+      DefaultValue::String(string) => Some(format!("{:?}", string)),
+      DefaultValue::Array(values) => {
+        let values = values
+          .iter()
+          .map(DefaultValue::to_json)
+          .collect::<Option<Vec<String>>>()?;
+        Some(format!("[{}]", values.join(", ")))
+      }
+      DefaultValue::Hash(pairs) if pairs.is_empty() => Some("{}".to_string()),
+      DefaultValue::Hash(pairs) => {
+        let pairs = pairs
+          .iter()
+          .map(|(key, value)| Some(format!("{:?}: {}", key, value.to_json()?)))
+          .collect::<Option<Vec<String>>>()?;
+        Some(format!("{{ {} }}", pairs.join(", ")))
+      }
+      DefaultValue::Expression(_) => None,
+    }
+  }
+
+  // The default written out as a number, if it is one. Rails dumps decimal
+  // defaults as strings, e.g. `default: "0.0"`.
+  pub fn as_number(&self) -> Option<String> {
+    match self {
+      DefaultValue::Integer(integer) => Some(integer.to_string()),
+      DefaultValue::Float(float) => Some(float.to_string()),
+      DefaultValue::String(string) if string.parse::<f64>().is_ok() => Some(string.clone()),
+      _ => None,
+    }
+  }
+
+  // The default as a value of a numeric column's type, so `default: 0` on a
+  // float column is a float and `default: "5"` on a decimal stored as an
+  // integer is an integer. Decimals are kept as the digits they were written with.
+  pub fn to_numeric(&self, numeric_type: NumericType) -> Option<DefaultValue> {
+    if let DefaultValue::Array(values) = self {
+      let values = values
+        .iter()
+        .map(|value| value.to_numeric(numeric_type))
+        .collect::<Option<Vec<DefaultValue>>>()?;
+      return Some(DefaultValue::Array(values));
+    }
+    if let DefaultValue::Expression(_) = self {
+      return Some(self.clone());
+    }
+    let number = self.as_number()?;
+    match numeric_type {
+      NumericType::SmallInt | NumericType::Int | NumericType::BigInt => {
+        let float = number
+          .parse::<f64>()
+          .ok()
+          .filter(|float| float.fract() == 0.0)?;
+        Some(DefaultValue::Integer(
+          number.parse().unwrap_or(float as i64),
+        ))
+      }
+      NumericType::Float => number.parse().ok().map(DefaultValue::Float),
+      NumericType::Decimal => Some(DefaultValue::String(number)),
+    }
+  }
+}
+
+impl ColumnData {
+  // A column the database fills in for us when a row is inserted
+  pub fn is_server_generated(&self) -> bool {
+    matches!(self.options.default, Some(DefaultValue::Expression(_)))
+  }
+
+  pub fn is_primary_key(&self) -> bool {
+    self.value_type == RailsColumn::PrimaryKey || self.options.primary_key
+  }
+
+  // Rails fills these in itself whenever a record is saved
+  pub fn is_timestamp(&self) -> bool {
+    matches!(
+      self.name.as_str(),
+      "created_at" | "updated_at" | "created_on" | "updated_on"
+    )
+  }
+
+  // Auto-incrementing keys and keys with an expression default belong to the database
+  fn is_generated_primary_key(&self) -> bool {
+    let auto_increment = matches!(
+      self.value_type,
+      RailsColumn::PrimaryKey | RailsColumn::Integer | RailsColumn::Bigint
+    );
+    self.is_primary_key() && (auto_increment || self.is_server_generated())
+  }
+
+  // The column's default, with numbers converted to the column's numeric type
+  pub fn typed_default(&self, adapter: Adapter) -> Option<DefaultValue> {
+    let default = self.options.default.as_ref()?;
+    match self.numeric_type(adapter) {
+      Some(numeric_type) => default.to_numeric(numeric_type),
+      None => Some(default.clone()),
+    }
+  }
+
+  // Narrows the column's numeric type using `limit:`, `precision:` and `scale:`
+  pub fn numeric_type(&self, adapter: Adapter) -> Option<NumericType> {
+    let default = self.value_type.numeric_type(adapter)?;
+    let options = &self.options;
+    let numeric_type = match (default, options.limit, options.precision, options.scale) {
+      (NumericType::SmallInt | NumericType::Int | NumericType::BigInt, Some(limit), _, _) => {
+        NumericType::from_limit(limit)
+      }
+      // A decimal without any fractional digits is just a big integer
+      (NumericType::Decimal, _, Some(precision), None | Some(0)) => {
+        NumericType::from_precision(precision).unwrap_or(default)
+      }
+      _ => default,
+    };
+    Some(numeric_type)
+  }
 }
 
 #[derive(Debug)]
 pub struct Table {
   name: String,
   columns: Vec<ColumnData>,
+  indexes: Vec<Index>,
 }
 
 impl PartialEq for Table {
@@ -42,7 +291,7 @@ impl PartialEq for Table {
     if self.columns.len() != other.columns.len() {
       return false;
     }
-    let eq_names = self.name == other.name;
+    let eq_names = self.name == other.name && self.indexes == other.indexes;
     let contains_self = self.columns.iter().all(|column| other.columns.contains(column));
     let contains_other = other.columns.iter().all(|column| self.columns.contains(column));
 
@@ -50,12 +299,177 @@ impl PartialEq for Table {
   }
 }
 
+impl Table {
+  // Whether any column has a default the generators can fill in themselves,
+  // rather than one only the database can work out
+  pub fn has_literal_defaults(&self) -> bool {
+    self
+      .columns
+      .iter()
+      .any(|column| column.options.default.is_some() && !column.is_server_generated())
+  }
+
+  // What a type generated from the table is named, before each format puts
+  // it in its own case, e.g. `people` holds a `person`
+  pub fn record_name(&self, config: &Config) -> String {
+    match config.type_names {
+      TypeNames::Singular => config.inflections.singularize(&self.name),
+      TypeNames::Plural => self.name.clone(),
+    }
+  }
+
+  // The record name in PascalCase, as Rust, TypeScript and Go name their
+  // types. None of them allow a leading digit, but they all allow a leading _.
+  pub fn type_name(&self, config: &Config) -> String {
+    let name = Case::Pascal.convert(&self.record_name(config), &config.acronyms);
+    if name.starts_with(|first: char| first.is_ascii_digit()) {
+      format!("_{}", name)
+    } else {
+      name
+    }
+  }
+
+  // The table's primary key, when it's a single column
+  pub fn primary_key(&self) -> Option<&ColumnData> {
+    let mut keys = self.columns.iter().filter(|column| column.is_primary_key());
+    match (keys.next(), keys.next()) {
+      (Some(key), None) => Some(key),
+      _ => None,
+    }
+  }
+
+  // The columns a variant can write to, paired with whether each one can be left out
+  pub fn variant_columns(&self, variant: Variant) -> Vec<(&ColumnData, bool)> {
+    self
+      .columns
+      .iter()
+      .filter(|column| !column.is_generated_primary_key())
+      .map(|column| {
+        let optional = match variant {
+          Variant::Insert => {
+            column.nullable || column.options.default.is_some() || column.is_timestamp()
+          }
+          Variant::Patch => true,
+        };
+        (column, optional)
+      })
+      .collect()
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AssociationKind {
+  BelongsTo,
+  HasMany,
+}
+
+// A relationship between two tables, named the way Rails would name it on the model
+#[derive(Debug, PartialEq)]
+pub struct Association<'a> {
+  pub kind: AssociationKind,
+  pub name: String,
+  pub table: &'a Table,
+  // The foreign key behind it, on this table for a belongs_to and on the
+  // other one for a has_many
+  pub column: &'a ColumnData,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Database(Vec<Table>);
 
+impl Database {
+  fn table(&self, name: &str) -> Option<&Table> {
+    self.0.iter().find(|table| table.name == name)
+  }
+
+  // Every enum type used by a column, in the order they're first used
+  pub fn enums(&self) -> Vec<&EnumType> {
+    let mut enums: Vec<&EnumType> = vec![];
+    let used = self
+      .0
+      .iter()
+      .flat_map(|table| table.columns.iter())
+      .filter_map(|column| column.options.enum_type.as_ref());
+    for enum_type in used {
+      if !enums.iter().any(|known| known.name == enum_type.name) {
+        enums.push(enum_type);
+      }
+    }
+    enums
+  }
+
+  // Each of `table`'s columns that holds a row's id, paired with the table
+  // the row is in. That's its own primary key and any foreign key to a
+  // table's primary key, as long as the key is a single column.
+  pub fn keys<'a>(&'a self, table: &'a Table) -> Vec<(&'a ColumnData, &'a Table)> {
+    table
+      .columns
+      .iter()
+      .filter_map(|column| {
+        let (owner, key) = match &column.options.foreign_key {
+          Some(foreign_key) => (self.table(&foreign_key.table)?, &foreign_key.primary_key),
+          None => (table, &column.name),
+        };
+        let primary_key = owner.primary_key()?;
+        (primary_key.name == *key).then_some((column, owner))
+      })
+      .collect()
+  }
+
+  // Every belongs_to and has_many association of `table`, worked out from
+  // the foreign keys on its own columns and the columns pointing back at it
+  pub fn associations<'a>(&'a self, table: &'a Table) -> Vec<Association<'a>> {
+    let belongs_to = table.columns.iter().filter_map(|column| {
+      let foreign_key = column.options.foreign_key.as_ref()?;
+      Some(Association {
+        kind: AssociationKind::BelongsTo,
+        name: column.name.strip_suffix("_id")?.to_string(),
+        table: self.table(&foreign_key.table)?,
+        column,
+      })
+    });
+
+    let references = self.0.iter().flat_map(|other| {
+      other
+        .columns
+        .iter()
+        .filter(|column| {
+          let foreign_key = column.options.foreign_key.as_ref();
+          foreign_key.is_some_and(|foreign_key| foreign_key.table == table.name)
+        })
+        .map(move |column| (other, column))
+    });
+    let has_many = references.clone().map(|(other, column)| {
+      // Two foreign keys from the same table need telling apart, e.g.
+      // posts.author_id and posts.editor_id become posts_as_author and posts_as_editor
+      let ambiguous = references
+        .clone()
+        .filter(|(from, _)| from.name == other.name)
+        .count()
+        > 1;
+      let name = match column.name.strip_suffix("_id") {
+        Some(association) if ambiguous => format!("{}_as_{}", other.name, association),
+        _ => other.name.clone(),
+      };
+      Association {
+        kind: AssociationKind::HasMany,
+        name,
+        table: other,
+        column,
+      }
+    });
+
+    belongs_to.chain(has_many).collect()
+  }
+}
+
 mod tests {
   #![allow(unused_imports)]
-  use crate::{ColumnData, Database, RailsColumn, Table};
+  use crate::{
+    config::{Adapter, Config, TypeNames},
+    AssociationKind, ColumnData, ColumnOptions, Database, DefaultValue, ForeignKey, NumericType,
+    RailsColumn, Table, Variant,
+  };
 
   #[test]
   fn rails_columns_are_equal() {
@@ -73,12 +487,14 @@ mod tests {
       ColumnData {
         name: "b".to_string(),
         value_type: RailsColumn::PrimaryKey,
-        nullable: true
+        nullable: true,
+        options: ColumnOptions::default()
       },
       ColumnData {
         name: "b".to_string(),
         value_type: RailsColumn::PrimaryKey,
-        nullable: true
+        nullable: true,
+        options: ColumnOptions::default()
       }
     )
   }
@@ -89,12 +505,14 @@ mod tests {
       ColumnData {
         name: "b".to_string(),
         value_type: RailsColumn::PrimaryKey,
-        nullable: true
+        nullable: true,
+        options: ColumnOptions::default()
       },
       ColumnData {
         name: "chuck_testa".to_string(),
         value_type: RailsColumn::PrimaryKey,
-        nullable: true
+        nullable: true,
+        options: ColumnOptions::default()
       }
     )
   }
@@ -106,16 +524,20 @@ mod tests {
         columns: vec![ColumnData {
           name: "b".to_string(),
           value_type: RailsColumn::PrimaryKey,
-          nullable: true
-        }]
+          nullable: true,
+          options: ColumnOptions::default()
+        }],
+        indexes: vec![],
       },
       Table {
         name: "sample_schema".to_string(),
         columns: vec![ColumnData {
           name: "b".to_string(),
           value_type: RailsColumn::PrimaryKey,
-          nullable: true
-        }]
+          nullable: true,
+          options: ColumnOptions::default()
+        }],
+        indexes: vec![],
       }
     )
   }
@@ -125,11 +547,13 @@ mod tests {
     assert_eq!(
       Table {
         name: "sample_schema".to_string(),
-        columns: vec![]
+        columns: vec![],
+        indexes: vec![],
       },
       Table {
         name: "sample_schema".to_string(),
-        columns: vec![]
+        columns: vec![],
+        indexes: vec![],
       }
     )
   }
@@ -139,15 +563,18 @@ mod tests {
     assert_ne!(
       Table {
         name: "sample_schema".to_string(),
-        columns: vec![]
+        columns: vec![],
+        indexes: vec![],
       },
       Table {
         name: "sample_schema".to_string(),
         columns: vec![ColumnData {
           name: "b".to_string(),
           value_type: RailsColumn::PrimaryKey,
-          nullable: true
-        }]
+          nullable: true,
+          options: ColumnOptions::default()
+        }],
+        indexes: vec![],
       }
     )
   }
@@ -159,16 +586,20 @@ mod tests {
         columns: vec![ColumnData {
           name: "b".to_string(),
           value_type: RailsColumn::PrimaryKey,
-          nullable: true
-        }]
+          nullable: true,
+          options: ColumnOptions::default()
+        }],
+        indexes: vec![],
       },
       Table {
         name: "also_my_table".to_string(),
         columns: vec![ColumnData {
           name: "b".to_string(),
           value_type: RailsColumn::PrimaryKey,
-          nullable: true
-        }]
+          nullable: true,
+          options: ColumnOptions::default()
+        }],
+        indexes: vec![],
       }
     )
   }
@@ -181,16 +612,20 @@ mod tests {
         columns: vec![ColumnData {
           name: "b".to_string(),
           value_type: RailsColumn::PrimaryKey,
-          nullable: true
-        }]
+          nullable: true,
+          options: ColumnOptions::default()
+        }],
+        indexes: vec![],
       }]),
       Database(vec![Table {
         name: "sample_schema".to_string(),
         columns: vec![ColumnData {
           name: "a".to_string(),
           value_type: RailsColumn::PrimaryKey,
-          nullable: true
-        }]
+          nullable: true,
+          options: ColumnOptions::default()
+        }],
+        indexes: vec![],
       }])
     );
   }
@@ -203,17 +638,241 @@ mod tests {
         columns: vec![ColumnData {
           name: "a".to_string(),
           value_type: RailsColumn::PrimaryKey,
-          nullable: true
-        }]
+          nullable: true,
+          options: ColumnOptions::default()
+        }],
+        indexes: vec![],
       }]),
       Database(vec![Table {
         name: "sample_schema".to_string(),
         columns: vec![ColumnData {
           name: "a".to_string(),
           value_type: RailsColumn::PrimaryKey,
-          nullable: true
-        }]
+          nullable: true,
+          options: ColumnOptions::default()
+        }],
+        indexes: vec![],
       }])
     );
   }
+
+  #[test]
+  fn integers_are_sized_by_their_limit() {
+    let column = |value_type, limit| ColumnData {
+      name: "a".to_string(),
+      value_type,
+      nullable: true,
+      options: ColumnOptions {
+        limit,
+        ..ColumnOptions::default()
+      },
+    };
+    let adapter = Adapter::Postgresql;
+    assert_eq!(
+      column(RailsColumn::Integer, None).numeric_type(adapter),
+      Some(NumericType::Int)
+    );
+    assert_eq!(
+      column(RailsColumn::Integer, Some(2)).numeric_type(adapter),
+      Some(NumericType::SmallInt)
+    );
+    assert_eq!(
+      column(RailsColumn::Integer, Some(8)).numeric_type(adapter),
+      Some(NumericType::BigInt)
+    );
+    assert_eq!(
+      column(RailsColumn::Bigint, None).numeric_type(adapter),
+      Some(NumericType::BigInt)
+    );
+    assert_eq!(
+      column(RailsColumn::Integer, None).numeric_type(Adapter::Sqlite),
+      Some(NumericType::BigInt)
+    );
+    assert_eq!(
+      column(RailsColumn::String, Some(255)).numeric_type(adapter),
+      None
+    );
+  }
+
+  #[test]
+  fn decimals_without_a_scale_become_integers() {
+    let column = |precision, scale| ColumnData {
+      name: "a".to_string(),
+      value_type: RailsColumn::Decimal,
+      nullable: true,
+      options: ColumnOptions {
+        precision,
+        scale,
+        ..ColumnOptions::default()
+      },
+    };
+    let adapter = Adapter::Postgresql;
+    assert_eq!(
+      column(None, None).numeric_type(adapter),
+      Some(NumericType::Decimal)
+    );
+    assert_eq!(
+      column(Some(10), Some(2)).numeric_type(adapter),
+      Some(NumericType::Decimal)
+    );
+    assert_eq!(
+      column(Some(4), Some(0)).numeric_type(adapter),
+      Some(NumericType::SmallInt)
+    );
+    assert_eq!(
+      column(Some(9), None).numeric_type(adapter),
+      Some(NumericType::Int)
+    );
+    assert_eq!(
+      column(Some(18), Some(0)).numeric_type(adapter),
+      Some(NumericType::BigInt)
+    );
+    assert_eq!(
+      column(Some(30), Some(0)).numeric_type(adapter),
+      Some(NumericType::Decimal)
+    );
+  }
+
+  #[test]
+  fn inserts_leave_out_generated_keys_and_make_defaulted_columns_optional() {
+    let column = |name: &str, value_type, nullable, default| ColumnData {
+      name: name.to_string(),
+      value_type,
+      nullable,
+      options: ColumnOptions {
+        default,
+        ..ColumnOptions::default()
+      },
+    };
+    let table = Table {
+      name: "users".to_string(),
+      columns: vec![
+        column("id", RailsColumn::PrimaryKey, false, None),
+        column("name", RailsColumn::String, false, None),
+        column("nickname", RailsColumn::String, true, None),
+        column(
+          "role",
+          RailsColumn::String,
+          false,
+          Some(DefaultValue::String("member".to_string())),
+        ),
+        column("created_at", RailsColumn::Datetime, false, None),
+      ],
+      indexes: vec![],
+    };
+    let optional = |variant| {
+      table
+        .variant_columns(variant)
+        .iter()
+        .map(|(column, optional)| (column.name.as_str(), *optional))
+        .collect::<Vec<(&str, bool)>>()
+    };
+    assert_eq!(
+      optional(Variant::Insert),
+      vec![
+        ("name", false),
+        ("nickname", true),
+        ("role", true),
+        ("created_at", true)
+      ]
+    );
+    assert_eq!(
+      optional(Variant::Patch),
+      vec![
+        ("name", true),
+        ("nickname", true),
+        ("role", true),
+        ("created_at", true)
+      ]
+    );
+  }
+
+  #[test]
+  fn foreign_keys_become_associations_on_both_tables() {
+    let column = |name: &str, references: Option<&str>| ColumnData {
+      name: name.to_string(),
+      value_type: RailsColumn::Bigint,
+      nullable: false,
+      options: ColumnOptions {
+        foreign_key: references.map(|table| ForeignKey {
+          table: table.to_string(),
+          primary_key: "id".to_string(),
+        }),
+        ..ColumnOptions::default()
+      },
+    };
+    let database = Database(vec![
+      Table {
+        name: "users".to_string(),
+        columns: vec![column("id", None)],
+        indexes: vec![],
+      },
+      Table {
+        name: "posts".to_string(),
+        columns: vec![
+          column("author_id", Some("users")),
+          column("editor_id", Some("users")),
+        ],
+        indexes: vec![],
+      },
+      Table {
+        name: "comments".to_string(),
+        columns: vec![column("user_id", Some("users"))],
+        indexes: vec![],
+      },
+    ]);
+    let associations = |table| {
+      database
+        .associations(&database.0[table])
+        .into_iter()
+        .map(|association| {
+          (
+            association.kind,
+            association.name,
+            association.table.name.as_str(),
+          )
+        })
+        .collect::<Vec<(AssociationKind, String, &str)>>()
+    };
+
+    assert_eq!(
+      associations(0),
+      vec![
+        (
+          AssociationKind::HasMany,
+          "posts_as_author".to_string(),
+          "posts"
+        ),
+        (
+          AssociationKind::HasMany,
+          "posts_as_editor".to_string(),
+          "posts"
+        ),
+        (AssociationKind::HasMany, "comments".to_string(), "comments"),
+      ]
+    );
+    assert_eq!(
+      associations(1),
+      vec![
+        (AssociationKind::BelongsTo, "author".to_string(), "users"),
+        (AssociationKind::BelongsTo, "editor".to_string(), "users"),
+      ]
+    );
+  }
+
+  #[test]
+  fn types_are_named_after_a_single_row() {
+    let table = |name: &str| Table {
+      name: name.to_string(),
+      columns: vec![],
+      indexes: vec![],
+    };
+    let mut config = Config::default();
+    assert_eq!(table("people").record_name(&config), "person");
+    assert_eq!(table("line_items").record_name(&config), "line_item");
+    assert_eq!(table("categories").record_name(&config), "category");
+
+    config.type_names = TypeNames::Plural;
+    assert_eq!(table("people").record_name(&config), "people");
+  }
 }
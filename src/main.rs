@@ -1,7 +1,11 @@
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::prelude::*;
 use std::path::Path;
-use turbine::{rust::ToRust, spec::ToSpec, typescript::ToTypeScript, go::ToGo, Database};
+use turbine::{
+  config::Config, diesel::ToDiesel, drizzle::ToDrizzle, go::ToGo, kysely::ToKysely,
+  prisma::ToPrisma, rust::ToRust, sea_orm::ToSeaOrm, spec::ToSpec, typeorm::ToTypeOrm,
+  typescript::ToTypeScript, zod::ToZod, Database,
+};
 
 use clap::{Parser, ValueEnum};
 
@@ -11,6 +15,13 @@ enum FormatTypes {
   Rust,
   TypeScript,
   Go,
+  Diesel,
+  SeaOrm,
+  Zod,
+  Kysely,
+  Drizzle,
+  Prisma,
+  TypeOrm,
 }
 
 #[derive(Parser, Debug)]
@@ -30,6 +41,10 @@ struct Opts {
   /// Where to save the output. If no name is specified it defaults to stdout
   #[arg(short, long)]
   output: Option<String>,
+
+  /// A turbine.toml file with project specific settings for each format
+  #[arg(short, long)]
+  config: Option<String>,
 }
 
 fn main() {
@@ -38,7 +53,7 @@ fn main() {
   let path = Path::new(&opts.schema);
   let display = path.display();
 
-  let mut file = match File::open(&path) {
+  let mut file = match File::open(path) {
     Err(why) => {
       eprintln!("couldn't open {}: {}", display, why);
       return;
@@ -51,18 +66,44 @@ fn main() {
     eprintln!("couldn't read {}: {}", display, why);
     return;
   }
+  let config = match opts.config {
+    Some(name) => match Config::from_path(Path::new(&name)) {
+      Err(why) => {
+        eprintln!("couldn't load config {}: {}", name, why);
+        return;
+      }
+      Ok(config) => config,
+    },
+    None => Config::default(),
+  };
+
+  let mut database = Database::from(&schema);
+  if config.relationships.infer {
+    for warning in database.infer_foreign_keys(&config) {
+      eprintln!("warning: {}", warning);
+    }
+  }
+
   let spec = match opts.format {
-    FormatTypes::Spec => Database::from(&schema).to_spec(),
-    FormatTypes::Rust => Database::from(&schema).to_rust(),
-    FormatTypes::Go => Database::from(&schema).to_go(),
-    FormatTypes::TypeScript => Database::from(&schema).to_typescript(),
+    FormatTypes::Spec => database.to_spec(&config),
+    FormatTypes::Rust => database.to_rust(&config),
+    FormatTypes::Go => database.to_go(&config),
+    FormatTypes::TypeScript => database.to_typescript(&config),
+    FormatTypes::Diesel => database.to_diesel(&config),
+    FormatTypes::SeaOrm => database.to_sea_orm(&config),
+    FormatTypes::Zod => database.to_zod(&config),
+    FormatTypes::Kysely => database.to_kysely(&config),
+    FormatTypes::Drizzle => database.to_drizzle(&config),
+    FormatTypes::Prisma => database.to_prisma(&config),
+    // Entities are written one per file, into the output directory
+    FormatTypes::TypeOrm => return write_files(&database.to_typeorm(&config), opts.output),
   };
 
   match opts.output {
     Some(name) => {
       let output_path = Path::new(&name);
       let output_display = output_path.display();
-      let mut file = match File::create(&output_path) { // Use File::create to write
+      let mut file = match File::create(output_path) { // Use File::create to write
         Err(why) => {
           eprintln!("couldn't create {}: {}", output_display, why); // Updated error message
           return;
@@ -77,3 +118,27 @@ fn main() {
     None => println!("{}", spec),
   }
 }
+
+// Writes each file into `output` as a directory, or prints them one after
+// another, each headed by its name
+fn write_files(files: &[(String, String)], output: Option<String>) {
+  let Some(directory) = output else {
+    let files = files
+      .iter()
+      .map(|(name, contents)| format!("// {}\n{}", name, contents))
+      .collect::<Vec<String>>();
+    println!("{}", files.join("\n\n"));
+    return;
+  };
+  let directory = Path::new(&directory);
+  if let Err(why) = fs::create_dir_all(directory) {
+    eprintln!("couldn't create {}: {}", directory.display(), why);
+    return;
+  }
+  for (name, contents) in files {
+    let path = directory.join(name);
+    if let Err(why) = fs::write(&path, format!("{}\n", contents)) {
+      eprintln!("couldn't write to {}: {}", path.display(), why);
+    }
+  }
+}
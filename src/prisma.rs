@@ -0,0 +1,576 @@
+use super::case::Case;
+use super::config::{Adapter, Config};
+use super::inflector;
+use super::ColumnData;
+use super::Database;
+use super::DefaultValue;
+use super::EnumType;
+use super::Index;
+use super::NumericType;
+use super::RailsColumn;
+use super::Table;
+
+pub trait ToPrisma {
+  fn to_prisma(&self, config: &Config) -> String;
+}
+
+// A schema.prisma for the database as it is, written the way `prisma db pull`
+// would, with camelCase fields mapped onto Rails' snake_case columns
+impl ToPrisma for Database {
+  fn to_prisma(&self, config: &Config) -> String {
+    let provider = match config.adapter {
+      Adapter::Postgresql => "postgresql",
+      Adapter::Mysql => "mysql",
+      Adapter::Sqlite => "sqlite",
+    };
+    let mut blocks = vec![
+      format!(
+        "datasource db {{\n  provider = {:?}\n  url      = env(\"DATABASE_URL\")\n}}",
+        provider
+      ),
+      "generator client {\n  provider = \"prisma-client-js\"\n}".to_string(),
+    ];
+    blocks.extend(
+      self
+        .enums()
+        .into_iter()
+        .filter(|enum_type| !enum_type.values.is_empty())
+        .map(|enum_type| enum_to_prisma(enum_type, config)),
+    );
+    blocks.extend(self.0.iter().map(|table| model(table, self, config)));
+    blocks.join("\n\n")
+  }
+}
+
+// Prisma names have to start with a letter, so anything else gets a prefix
+fn identifier(name: String, prefix: &str) -> String {
+  if name.starts_with(|first: char| first.is_ascii_alphabetic()) {
+    name
+  } else {
+    format!("{}{}", prefix, name.trim_start_matches('_'))
+  }
+}
+
+fn model_name(table: &Table, config: &Config) -> String {
+  identifier(table.type_name(config), "Model")
+}
+
+fn enum_name(enum_type: &EnumType, config: &Config) -> String {
+  identifier(
+    Case::Pascal.convert(&enum_type.name, &config.acronyms),
+    "Enum",
+  )
+}
+
+fn enum_value(value: &str) -> String {
+  identifier(Case::Snake.convert(value, &[]), "value_")
+}
+
+fn field_name(name: &str, config: &Config) -> String {
+  identifier(Case::Camel.convert(name, &config.acronyms), "column")
+}
+
+// Writes `@map` or `@@map` when Prisma's name isn't the database's
+fn map(prisma_name: &str, name: &str, attribute: &str) -> Option<String> {
+  (prisma_name != name).then(|| format!("{}({:?})", attribute, name))
+}
+
+fn enum_to_prisma(enum_type: &EnumType, config: &Config) -> String {
+  let name = enum_name(enum_type, config);
+  let mut lines = enum_type
+    .values
+    .iter()
+    .map(|value| {
+      let prisma_value = enum_value(value);
+      match map(&prisma_value, value, "@map") {
+        Some(map) => format!("  {} {}", prisma_value, map),
+        None => format!("  {}", prisma_value),
+      }
+    })
+    .collect::<Vec<String>>();
+  if let Some(map) = map(&name, &enum_type.name, "@@map") {
+    lines.push(String::new());
+    lines.push(format!("  {}", map));
+  }
+  format!("enum {} {{\n{}\n}}", name, lines.join("\n"))
+}
+
+// A field's name, type and attributes, lined up in columns like `prisma format` does
+struct Field {
+  name: String,
+  field_type: String,
+  attributes: Vec<String>,
+}
+
+fn aligned(fields: &[Field]) -> Vec<String> {
+  let name_width = fields
+    .iter()
+    .map(|field| field.name.len())
+    .max()
+    .unwrap_or(0);
+  let type_width = fields
+    .iter()
+    .map(|field| field.field_type.len())
+    .max()
+    .unwrap_or(0);
+  fields
+    .iter()
+    .map(|field| {
+      let line = format!(
+        "  {:name_width$} {:type_width$} {}",
+        field.name,
+        field.field_type,
+        field.attributes.join(" ")
+      );
+      line.trim_end().to_string()
+    })
+    .collect()
+}
+
+// The scalar type of a column along with the native type it's stored as,
+// when that isn't the one Prisma picks for the scalar
+fn scalar_type(column: &ColumnData, config: &Config) -> (String, Option<String>) {
+  let options = &column.options;
+  let native = |native: String| match config.adapter {
+    Adapter::Sqlite => None,
+    _ => Some(format!("@db.{}", native)),
+  };
+  match (&column.value_type, config.adapter) {
+    (RailsColumn::Enum, _) => match &options.enum_type {
+      Some(enum_type) if !enum_type.values.is_empty() => (enum_name(enum_type, config), None),
+      _ => ("String".to_string(), None),
+    },
+    (RailsColumn::Decimal | RailsColumn::Numeric, _) => (
+      "Decimal".to_string(),
+      options.precision.and_then(|precision| {
+        native(format!(
+          "Decimal({}, {})",
+          precision,
+          options.scale.unwrap_or(0)
+        ))
+      }),
+    ),
+    (RailsColumn::String, Adapter::Mysql) => (
+      "String".to_string(),
+      native(format!("VarChar({})", options.limit.unwrap_or(255))),
+    ),
+    (RailsColumn::String, _) => (
+      "String".to_string(),
+      native(match options.limit {
+        Some(limit) => format!("VarChar({})", limit),
+        None => "VarChar".to_string(),
+      }),
+    ),
+    (RailsColumn::Text, Adapter::Mysql) => ("String".to_string(), native("Text".to_string())),
+    (RailsColumn::Text, _) => ("String".to_string(), None),
+    (RailsColumn::Uuid, Adapter::Mysql) => ("String".to_string(), native("Char(36)".to_string())),
+    (RailsColumn::Uuid, _) => ("String".to_string(), native("Uuid".to_string())),
+    (RailsColumn::Datetime, Adapter::Mysql) => (
+      "DateTime".to_string(),
+      native(format!("DateTime({})", options.precision.unwrap_or(6))),
+    ),
+    (RailsColumn::Datetime, _) => (
+      "DateTime".to_string(),
+      native(format!("Timestamp({})", options.precision.unwrap_or(6))),
+    ),
+    (RailsColumn::Date, _) => ("DateTime".to_string(), native("Date".to_string())),
+    (RailsColumn::Time, _) => (
+      "DateTime".to_string(),
+      native(match options.precision {
+        Some(precision) => format!("Time({})", precision),
+        None => "Time".to_string(),
+      }),
+    ),
+    (RailsColumn::Binary, Adapter::Mysql) => ("Bytes".to_string(), native("Blob".to_string())),
+    (RailsColumn::Binary, _) => ("Bytes".to_string(), None),
+    (RailsColumn::Boolean, _) => ("Boolean".to_string(), None),
+    (RailsColumn::JsonB, _) => ("Json".to_string(), None),
+    (RailsColumn::HStore, _) => ("Unsupported(\"hstore\")".to_string(), None),
+    (_, Adapter::Sqlite) => match column.numeric_type(config.adapter) {
+      Some(NumericType::Float) => ("Float".to_string(), None),
+      _ => ("Int".to_string(), None),
+    },
+    (_, Adapter::Mysql) if column.numeric_type(config.adapter) == Some(NumericType::Float) => {
+      ("Float".to_string(), native("Float".to_string()))
+    }
+    _ => match column.numeric_type(config.adapter) {
+      Some(NumericType::SmallInt) => ("Int".to_string(), native("SmallInt".to_string())),
+      Some(NumericType::BigInt) => ("BigInt".to_string(), None),
+      Some(NumericType::Float) => ("Float".to_string(), None),
+      _ => ("Int".to_string(), None),
+    },
+  }
+}
+
+// A literal default as Prisma writes it, or the expression the database evaluates
+fn default(column: &ColumnData, value: &DefaultValue, scalar_type: &str) -> Option<String> {
+  let literal = match (value, scalar_type) {
+    (_, unsupported) if unsupported.starts_with("Unsupported") => return None,
+    (DefaultValue::Expression(expression), _) => match expression.as_str() {
+      "now()" | "CURRENT_TIMESTAMP" => "now()".to_string(),
+      expression => format!("dbgenerated({:?})", expression),
+    },
+    (DefaultValue::String(value), scalar_type)
+      if column.value_type == RailsColumn::Enum && scalar_type != "String" =>
+    {
+      enum_value(value)
+    }
+    (value, "Decimal") => value.as_number()?,
+    // Prisma reads JSON defaults from a string
+    (value, "Json") => format!("{:?}", value.to_json()?),
+    (value, _) => value.to_json()?,
+  };
+  Some(format!("@default({})", literal))
+}
+
+fn index_name(index: &Index, table: &Table) -> String {
+  index
+    .name
+    .clone()
+    .unwrap_or_else(|| format!("index_{}_on_{}", table.name, index.columns.join("_and_")))
+}
+
+// Whether a single column index makes the column unique by itself
+fn is_unique(column: &ColumnData, table: &Table) -> bool {
+  table
+    .indexes
+    .iter()
+    .any(|index| index.unique && index.columns == [column.name.clone()])
+}
+
+fn has_primary_key(table: &Table) -> bool {
+  table.columns.iter().any(ColumnData::is_primary_key)
+}
+
+// A foreign key Prisma can follow: from a table it can load, named like a
+// belongs_to, to a table it can load
+struct Relation<'a> {
+  child: &'a Table,
+  column: &'a ColumnData,
+  parent: &'a Table,
+  name: &'a str,
+  // Set when the two tables have more than one relation between them, which
+  // Prisma can only tell apart by name
+  label: Option<String>,
+}
+
+fn relations<'a>(database: &'a Database, config: &Config) -> Vec<Relation<'a>> {
+  let mut relations = database
+    .0
+    .iter()
+    .filter(|child| has_primary_key(child))
+    .flat_map(|child| {
+      child.columns.iter().filter_map(move |column| {
+        let foreign_key = column.options.foreign_key.as_ref()?;
+        let parent = database
+          .0
+          .iter()
+          .find(|parent| parent.name == foreign_key.table)?;
+        let references = parent
+          .columns
+          .iter()
+          .find(|key| key.name == foreign_key.primary_key)?;
+        if !references.is_primary_key() {
+          return None;
+        }
+        Some(Relation {
+          child,
+          column,
+          parent,
+          name: column.name.strip_suffix("_id")?,
+          label: None,
+        })
+      })
+    })
+    .collect::<Vec<Relation>>();
+  let labels = relations
+    .iter()
+    .map(|relation| {
+      let between = relations
+        .iter()
+        .filter(|other| {
+          other.child.name == relation.child.name && other.parent.name == relation.parent.name
+        })
+        .count();
+      (between > 1 || relation.child.name == relation.parent.name).then(|| {
+        format!(
+          "{}{}",
+          model_name(relation.child, config),
+          Case::Pascal.convert(relation.name, &config.acronyms)
+        )
+      })
+    })
+    .collect::<Vec<Option<String>>>();
+  for (relation, label) in relations.iter_mut().zip(labels) {
+    relation.label = label;
+  }
+  relations
+}
+
+fn model(table: &Table, database: &Database, config: &Config) -> String {
+  let name = model_name(table, config);
+  let mut fields = table
+    .columns
+    .iter()
+    .map(|column| {
+      let (scalar_type, native) = scalar_type(column, config);
+      let field_type = match (column.options.array, column.nullable) {
+        // Lists can't be optional in Prisma, an empty list stands in for null
+        (true, _) => format!("{}[]", scalar_type),
+        (false, true) => format!("{}?", scalar_type),
+        (false, false) => scalar_type.clone(),
+      };
+      let name = field_name(&column.name, config);
+      let mut attributes = vec![];
+      if column.is_primary_key() {
+        attributes.push("@id".to_string());
+      }
+      let auto_increment = column.is_primary_key()
+        && column.options.default.is_none()
+        && matches!(
+          column.value_type,
+          RailsColumn::PrimaryKey | RailsColumn::Integer | RailsColumn::Bigint
+        );
+      if auto_increment {
+        attributes.push("@default(autoincrement())".to_string());
+      }
+      attributes.extend(
+        column
+          .options
+          .default
+          .as_ref()
+          .and_then(|value| default(column, value, &scalar_type)),
+      );
+      if is_unique(column, table) {
+        let index = table
+          .indexes
+          .iter()
+          .find(|index| index.unique && index.columns == [column.name.clone()]);
+        attributes
+          .extend(index.map(|index| format!("@unique(map: {:?})", index_name(index, table))));
+      }
+      attributes.extend(map(&name, &column.name, "@map"));
+      attributes.extend(native);
+      Field {
+        name,
+        field_type,
+        attributes,
+      }
+    })
+    .collect::<Vec<Field>>();
+
+  for relation in relations(database, config) {
+    if relation.child.name == table.name {
+      let parent = model_name(relation.parent, config);
+      let optional = if relation.column.nullable { "?" } else { "" };
+      let mut arguments = relation
+        .label
+        .iter()
+        .map(|label| format!("{:?}", label))
+        .collect::<Vec<String>>();
+      arguments.push(format!(
+        "fields: [{}]",
+        field_name(&relation.column.name, config)
+      ));
+      let references = relation
+        .column
+        .options
+        .foreign_key
+        .as_ref()
+        .map(|foreign_key| field_name(&foreign_key.primary_key, config))
+        .unwrap_or_default();
+      arguments.push(format!("references: [{}]", references));
+      fields.push(Field {
+        name: field_name(relation.name, config),
+        field_type: format!("{}{}", parent, optional),
+        attributes: vec![format!("@relation({})", arguments.join(", "))],
+      });
+    }
+    if relation.parent.name == table.name {
+      let child = model_name(relation.child, config);
+      // A unique foreign key can only point back at one row
+      let (field_type, children) = if is_unique(relation.column, relation.child) {
+        (
+          format!("{}?", child),
+          inflector::singularize(&relation.child.name),
+        )
+      } else {
+        (format!("{}[]", child), relation.child.name.clone())
+      };
+      let name = match &relation.label {
+        Some(_) => format!("{}_as_{}", children, relation.name),
+        None => children,
+      };
+      fields.push(Field {
+        name: field_name(&name, config),
+        field_type,
+        attributes: relation
+          .label
+          .iter()
+          .map(|label| format!("@relation({:?})", label))
+          .collect(),
+      });
+    }
+  }
+
+  let mut block_attributes = vec![];
+  for index in &table.indexes {
+    let columns = index
+      .columns
+      .iter()
+      .map(|name| {
+        table
+          .columns
+          .iter()
+          .find(|column| &column.name == name)
+          .map(|column| field_name(&column.name, config))
+      })
+      .collect::<Option<Vec<String>>>();
+    let name = index_name(index, table);
+    match columns {
+      None => block_attributes.push(format!(
+        "// {} is on an expression Prisma can't describe",
+        name
+      )),
+      Some(columns) if index.unique && columns.len() == 1 => (),
+      Some(columns) => block_attributes.push(format!(
+        "{}([{}], map: {:?})",
+        if index.unique { "@@unique" } else { "@@index" },
+        columns.join(", "),
+        name
+      )),
+    }
+  }
+  // Prisma Client can't work with a table it has no way to pick a row out of
+  if !has_primary_key(table) {
+    block_attributes.push("@@ignore".to_string());
+  }
+  block_attributes.extend(map(&name, &table.name, "@@map"));
+
+  let mut lines = aligned(&fields);
+  if !block_attributes.is_empty() {
+    lines.push(String::new());
+    lines.extend(
+      block_attributes
+        .into_iter()
+        .map(|attribute| format!("  {}", attribute)),
+    );
+  }
+  format!("model {} {{\n{}\n}}", name, lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    config::{Adapter, Config},
+    prisma::ToPrisma,
+    Database,
+  };
+
+  #[test]
+  fn tables_become_models_mapped_onto_the_rails_schema() {
+    let schema = "create_enum \"mood\", [\"happy\", \"sad\"]
+  create_table \"users\", force: :cascade do |t|
+    t.string \"email\", limit: 255, null: false
+    t.string \"first_name\"
+    t.enum \"mood\", enum_type: \"mood\", default: \"happy\", null: false
+    t.string \"tags\", array: true, default: [], null: false
+    t.decimal \"balance\", precision: 10, scale: 2, default: \"0.0\", null: false
+    t.jsonb \"settings\", default: {}, null: false
+    t.datetime \"created_at\", null: false
+    t.index [\"email\"], name: \"index_users_on_email\", unique: true
+  end
+  create_table \"posts\", id: :uuid, default: -> { \"gen_random_uuid()\" }, force: :cascade do |t|
+    t.bigint \"author_id\", null: false
+    t.bigint \"editor_id\"
+    t.datetime \"published_at\", default: -> { \"CURRENT_TIMESTAMP\" }
+    t.index [\"author_id\", \"published_at\"]
+  end
+  add_foreign_key \"posts\", \"users\", column: \"author_id\"
+  add_foreign_key \"posts\", \"users\", column: \"editor_id\"";
+    assert_eq!(
+      Database::from(schema).to_prisma(&Config::default()),
+      "datasource db {
+  provider = \"postgresql\"
+  url      = env(\"DATABASE_URL\")
+}
+
+generator client {
+  provider = \"prisma-client-js\"
+}
+
+enum Mood {
+  happy
+  sad
+
+  @@map(\"mood\")
+}
+
+model User {
+  id            BigInt   @id @default(autoincrement())
+  email         String   @unique(map: \"index_users_on_email\") @db.VarChar(255)
+  firstName     String?  @map(\"first_name\") @db.VarChar
+  mood          Mood     @default(happy)
+  tags          String[] @default([]) @db.VarChar
+  balance       Decimal  @default(0.0) @db.Decimal(10, 2)
+  settings      Json     @default(\"{}\")
+  createdAt     DateTime @map(\"created_at\") @db.Timestamp(6)
+  postsAsAuthor Post[]   @relation(\"PostAuthor\")
+  postsAsEditor Post[]   @relation(\"PostEditor\")
+
+  @@map(\"users\")
+}
+
+model Post {
+  id          String    @id @default(dbgenerated(\"gen_random_uuid()\")) @db.Uuid
+  authorId    BigInt    @map(\"author_id\")
+  editorId    BigInt?   @map(\"editor_id\")
+  publishedAt DateTime? @default(now()) @map(\"published_at\") @db.Timestamp(6)
+  author      User      @relation(\"PostAuthor\", fields: [authorId], references: [id])
+  editor      User?     @relation(\"PostEditor\", fields: [editorId], references: [id])
+
+  @@index([authorId, publishedAt], map: \"index_posts_on_author_id_and_published_at\")
+  @@map(\"posts\")
+}"
+    );
+  }
+
+  #[test]
+  fn native_types_follow_the_adapter() {
+    let schema = "create_table \"events\", id: false, force: :cascade do |t|
+    t.string \"name\", null: false
+    t.text \"notes\"
+    t.float \"score\"
+    t.datetime \"starts_at\", precision: 3, null: false
+    t.bigint \"parent_id\"
+    t.index \"lower(name)\", name: \"index_events_on_lower_name\"
+  end";
+    let config = Config {
+      adapter: Adapter::Mysql,
+      ..Config::default()
+    };
+    assert_eq!(
+      Database::from(schema).to_prisma(&config),
+      "datasource db {
+  provider = \"mysql\"
+  url      = env(\"DATABASE_URL\")
+}
+
+generator client {
+  provider = \"prisma-client-js\"
+}
+
+model Event {
+  name     String   @db.VarChar(255)
+  notes    String?  @db.Text
+  score    Float?   @db.Float
+  startsAt DateTime @map(\"starts_at\") @db.DateTime(3)
+  parentId BigInt?  @map(\"parent_id\")
+
+  // index_events_on_lower_name is on an expression Prisma can't describe
+  @@ignore
+  @@map(\"events\")
+}"
+    );
+  }
+}
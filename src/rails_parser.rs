@@ -1,10 +1,23 @@
-use std::{str::FromStr, vec};
+use std::{fmt, str::FromStr, sync::LazyLock, vec};
 
-use super::{ColumnData, Database, RailsColumn, Table};
+use regex::Regex;
+
+use super::{
+  inflector, ColumnData, ColumnOptions, Database, DefaultValue, EnumType, ForeignKey, Index,
+  RailsColumn, Table,
+};
 
 #[derive(Debug)]
 pub struct RailsParseError(String);
 
+impl fmt::Display for RailsParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl std::error::Error for RailsParseError {}
+
 impl FromStr for RailsColumn {
   type Err = RailsParseError;
 
@@ -24,80 +37,417 @@ impl FromStr for RailsColumn {
       "t.binary" => Ok(RailsColumn::Binary),
       "t.boolean" => Ok(RailsColumn::Boolean),
       "t.hstore" => Ok(RailsColumn::HStore),
-      "t.jsonb" => Ok(RailsColumn::HStore),
+      // Both hold JSON documents, which the generators read the same way
+      "t.json" | "t.jsonb" => Ok(RailsColumn::JsonB),
+      "t.uuid" => Ok(RailsColumn::Uuid),
+      "t.enum" => Ok(RailsColumn::Enum),
       token => Err(RailsParseError(format!("Unable to parse token: {}", token))),
     }
   }
 }
 
+// Drops a trailing `# comment` from a line, ignoring any `#` inside a string
+fn strip_comment(line: &str) -> &str {
+  let mut quote = None;
+  for (i, c) in line.char_indices() {
+    match (quote, c) {
+      (None, '"' | '\'') => quote = Some(c),
+      (Some(q), c) if q == c => quote = None,
+      (None, '#') => return &line[..i],
+      _ => (),
+    }
+  }
+  line
+}
+
+// Splits a ruby argument list on its top level commas, leaving strings,
+// arrays, hashes and lambdas in one piece
+fn split_arguments(arguments: &str) -> Vec<&str> {
+  let mut split = vec![];
+  let mut quote = None;
+  let mut depth = 0;
+  let mut start = 0;
+  for (i, c) in arguments.char_indices() {
+    match (quote, c) {
+      (Some(q), c) if q == c => quote = None,
+      (Some(_), _) => (),
+      (None, '"' | '\'') => quote = Some(c),
+      (None, '[' | '{' | '(') => depth += 1,
+      (None, ']' | '}' | ')') => depth -= 1,
+      (None, ',') if depth == 0 => {
+        split.push(arguments[start..i].trim());
+        start = i + 1;
+      }
+      _ => (),
+    }
+  }
+  split.push(arguments[start..].trim());
+  split.retain(|argument| !argument.is_empty());
+  split
+}
+
+// Separates positional arguments from `key: value` options
+fn parse_arguments(arguments: &str) -> (Vec<&str>, Vec<(&str, &str)>) {
+  let mut positional = vec![];
+  let mut options = vec![];
+  for argument in split_arguments(arguments) {
+    match argument.split_once(": ") {
+      Some((key, value)) if key.chars().all(|c| c.is_alphanumeric() || c == '_') => {
+        options.push((key, value.trim()))
+      }
+      _ => positional.push(argument),
+    }
+  }
+  (positional, options)
+}
+
+fn unquote(value: &str) -> String {
+  let quoted_with = |quote| value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote);
+  if quoted_with('"') || quoted_with('\'') {
+    value[1..value.len() - 1].replace("\\\"", "\"")
+  } else {
+    value.to_string()
+  }
+}
+
+// Reads the ruby literal given to `default:`. A `nil` default is the same as
+// having no default at all.
+fn parse_default(value: &str) -> Option<DefaultValue> {
+  let value = value.trim();
+  if let Some(lambda) = value.strip_prefix("->") {
+    let body = lambda.trim().trim_start_matches('{').trim_end_matches('}');
+    return Some(DefaultValue::Expression(unquote(body.trim())));
+  }
+
+  match value.chars().next()? {
+    '"' | '\'' => Some(DefaultValue::String(unquote(value))),
+    '[' => {
+      let elements = split_arguments(&value[1..value.len() - 1]);
+      Some(DefaultValue::Array(
+        elements.into_iter().filter_map(parse_default).collect(),
+      ))
+    }
+    '{' => {
+      let pairs = split_arguments(&value[1..value.len() - 1])
+        .into_iter()
+        .filter_map(|pair| pair.split_once("=>").or_else(|| pair.split_once(": ")))
+        .filter_map(|(key, value)| Some((unquote(key.trim()), parse_default(value)?)))
+        .collect();
+      Some(DefaultValue::Hash(pairs))
+    }
+    _ => match value {
+      "nil" => None,
+      "true" => Some(DefaultValue::Boolean(true)),
+      "false" => Some(DefaultValue::Boolean(false)),
+      number => {
+        let number = number.replace('_', "");
+        match number.parse::<i64>() {
+          Ok(integer) => Some(DefaultValue::Integer(integer)),
+          Err(_) => number.parse::<f64>().ok().map(DefaultValue::Float),
+        }
+      }
+    },
+  }
+}
+
+impl ColumnData {
+  fn from_arguments(value_type: RailsColumn, arguments: &str) -> Self {
+    let (positional, options) = parse_arguments(arguments);
+    let mut column = ColumnData {
+      value_type,
+      name: positional
+        .first()
+        .map(|name| unquote(name))
+        .unwrap_or_default(),
+      nullable: true,
+      options: ColumnOptions::default(),
+    };
+    for (key, value) in options {
+      match key {
+        "null" => column.nullable = value != "false",
+        "limit" => column.options.limit = value.parse().ok(),
+        "precision" => column.options.precision = value.parse().ok(),
+        "scale" => column.options.scale = value.parse().ok(),
+        "default" => column.options.default = parse_default(value),
+        "array" => column.options.array = value == "true",
+        "enum_type" => {
+          column.options.enum_type = Some(EnumType {
+            name: unquote(value),
+            values: vec![],
+          })
+        }
+        _ => (),
+      }
+    }
+    // Rails names the type after the column when it isn't given one
+    if column.value_type == RailsColumn::Enum && column.options.enum_type.is_none() {
+      column.options.enum_type = Some(EnumType {
+        name: column.name.clone(),
+        values: vec![],
+      });
+    }
+    column
+  }
+}
+
+impl EnumType {
+  // Reads a `create_enum "mood", ["happy", "sad"]` line
+  fn from_arguments(arguments: &str) -> Option<Self> {
+    let (positional, _) = parse_arguments(arguments);
+    let values = match parse_default(positional.get(1)?)? {
+      DefaultValue::Array(values) => values
+        .into_iter()
+        .filter_map(|value| match value {
+          DefaultValue::String(value) => Some(value),
+          _ => None,
+        })
+        .collect(),
+      _ => return None,
+    };
+    Some(EnumType {
+      name: unquote(positional.first()?),
+      values,
+    })
+  }
+}
+
+// A check constraint that limits a column to a list of values, written either
+// the way it was declared or the way Postgres dumps it back out, e.g.
+// `(status)::text = ANY ((ARRAY['draft'::character varying])::text[])`
+static ALLOWED_VALUES: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(r#"(?i)^\(*"?(\w+)"?\)?(?:::[\w ]+?)?\s*(?:IN\s*\(|=\s*ANY\s*\(+ARRAY\s*\[)(.*)$"#)
+    .expect("the allowed values pattern is valid")
+});
+
+static STRING_LITERAL: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"'((?:[^']|'')*)'").expect("the string literal pattern is valid"));
+
+// Reads the column and its values out of a check constraint like
+// `"status IN ('draft', 'published')"`. Anything combining more than one
+// condition is left alone.
+fn allowed_values(arguments: &str) -> Option<(String, Vec<String>)> {
+  let (positional, _) = parse_arguments(arguments);
+  let expression = unquote(positional.first()?);
+  let upper = expression.to_uppercase();
+  if upper.contains(" AND ") || upper.contains(" OR ") {
+    return None;
+  }
+  let captures = ALLOWED_VALUES.captures(&expression)?;
+  let values = STRING_LITERAL
+    .captures_iter(&captures[2])
+    .map(|value| value[1].replace("''", "'"))
+    .collect::<Vec<String>>();
+  if values.is_empty() {
+    return None;
+  }
+  Some((captures[1].to_string(), values))
+}
+
 impl Table {
-  fn from_tokens(mut tokens: Vec<&str>) -> (Option<Self>, Vec<&str>) {
-    let mut table = Table {
-      name: "".to_string(),
+  // Reads the table's name along with the primary key Rails adds for us,
+  // unless the table opts out with `id: false`
+  fn from_arguments(arguments: &str) -> (Self, Option<ColumnData>) {
+    let arguments = arguments.trim_end().trim_end_matches("|t|").trim_end();
+    let arguments = arguments.strip_suffix(" do").unwrap_or(arguments);
+    let (positional, options) = parse_arguments(arguments);
+    let table = Table {
+      name: positional
+        .first()
+        .map(|name| unquote(name))
+        .unwrap_or_default(),
       columns: vec![],
+      indexes: vec![],
     };
-    let matchers: &[_] = &['"', ','];
-    let mut inside_table_expression = false;
-    for i in 0..tokens.len() {
-      // Check to see if we're in a table expression
-      if !inside_table_expression {
-        match tokens[i] {
-          // If not and the next token is the table name
-          "create_table" => {
-            inside_table_expression = true;
-            table.name = tokens[i + 1].trim_matches(matchers).to_string();
-            continue;
-          }
 
-          // If not nothing we're looking at can be a valid column name
-          _ => continue,
-        }
+    let mut primary_key = ColumnData {
+      value_type: RailsColumn::PrimaryKey,
+      name: "id".to_string(),
+      nullable: false,
+      options: ColumnOptions {
+        primary_key: true,
+        ..ColumnOptions::default()
+      },
+    };
+    for (key, value) in options {
+      match (key, value) {
+        ("id", "false") => return (table, None),
+        ("id", ":serial" | ":integer") => primary_key.options.limit = Some(4),
+        ("id", ":uuid") => primary_key.value_type = RailsColumn::Uuid,
+        ("id", ":string") => primary_key.value_type = RailsColumn::String,
+        ("primary_key", name) => primary_key.name = unquote(name),
+        ("default", value) => primary_key.options.default = parse_default(value),
+        _ => (),
       }
+    }
+    (table, Some(primary_key))
+  }
+}
 
-      // Ok so we're inside a table declaration
-      let column = match (tokens[i], RailsColumn::from_str(tokens[i])) {
-        (_, Ok(value_type)) => ColumnData {
-          value_type,
-          name: tokens[i + 1].trim_matches(matchers).to_string(),
-          nullable: !(tokens[i + 2] == "null:" && tokens[i + 3] == "false"),
-        },
-        ("end", Err(_)) => return (Some(table), tokens.split_off(i + 1)),
+impl ForeignKey {
+  // Reads an `add_foreign_key "posts", "users", column: "author_id"` line,
+  // returning the table and column the key is declared on
+  fn from_arguments(arguments: &str) -> Option<(String, String, Self)> {
+    let (positional, options) = parse_arguments(arguments);
+    let from_table = unquote(positional.first()?);
+    let to_table = unquote(positional.get(1)?);
+    // Rails only writes out the column when it isn't the singular of the table plus _id
+    let mut column = format!("{}_id", inflector::singularize(&to_table));
+    let mut foreign_key = ForeignKey {
+      table: to_table,
+      primary_key: "id".to_string(),
+    };
+    for (key, value) in options {
+      match key {
+        "column" => column = unquote(value),
+        "primary_key" => foreign_key.primary_key = unquote(value),
+        _ => (),
+      }
+    }
+    Some((from_table, column, foreign_key))
+  }
+}
+
+impl Index {
+  // Reads the columns and options of a `t.index ["user_id", "created_at"], unique: true` line
+  fn from_arguments(arguments: &str) -> Option<Self> {
+    let (positional, options) = parse_arguments(arguments);
+    let columns = match parse_default(positional.first()?)? {
+      DefaultValue::String(column) => vec![column],
+      DefaultValue::Array(columns) => columns
+        .into_iter()
+        .filter_map(|column| match column {
+          DefaultValue::String(column) => Some(column),
+          _ => None,
+        })
+        .collect(),
+      _ => return None,
+    };
+    let mut index = Index {
+      name: None,
+      columns,
+      unique: false,
+    };
+    for (key, value) in options {
+      match key {
+        "name" => index.name = Some(unquote(value)),
+        "unique" => index.unique = value == "true",
+        _ => (),
+      }
+    }
+    Some(index)
+  }
+}
 
-        ("create_table", Err(_)) => panic!(
+impl Database {
+  pub fn from(schema: &str) -> Self {
+    let mut database: Database = Database(vec![]);
+    let mut table: Option<Table> = None;
+    let mut primary_key: Option<ColumnData> = None;
+    let mut foreign_keys = vec![];
+    let mut enums = vec![];
+    let mut checks = vec![];
+    let mut indexes = vec![];
+    for line in schema.lines() {
+      let line = strip_comment(line).trim();
+      let (keyword, arguments) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+      match (&mut table, keyword) {
+        (None, "create_table") => {
+          let (new_table, implicit_key) = Table::from_arguments(arguments);
+          table = Some(new_table);
+          primary_key = implicit_key;
+        }
+        (None, "add_foreign_key") => foreign_keys.extend(ForeignKey::from_arguments(arguments)),
+        (None, "create_enum") => enums.extend(EnumType::from_arguments(arguments)),
+        (None, "add_index") => {
+          if let Some((table, arguments)) = arguments.split_once(',') {
+            indexes.extend(Index::from_arguments(arguments).map(|index| (unquote(table), index)));
+          }
+        }
+        (None, "add_check_constraint") => {
+          if let Some((table, expression)) = arguments.split_once(',') {
+            checks.extend(
+              allowed_values(expression.trim())
+                .map(|(column, values)| (unquote(table), column, values)),
+            );
+          }
+        }
+        // Nothing outside of a create_table block can be a column
+        (None, _) => continue,
+        (Some(_), "create_table") => panic!(
           "{}",
           "Warning Invalid Rails Schema:\n Found create_table inside create_table block"
         ),
-        (_, Err(_)) => continue,
-      };
-
-      table.columns.push(column);
+        (Some(finished), "end") => {
+          // A t.primary_key declared in the block replaces the implicit one
+          if !finished.columns.iter().any(ColumnData::is_primary_key) {
+            finished.columns.splice(0..0, primary_key.take());
+          }
+          database.0.extend(table.take());
+        }
+        (Some(table), "t.index") => table.indexes.extend(Index::from_arguments(arguments)),
+        (Some(table), "t.check_constraint") => checks.extend(
+          allowed_values(arguments).map(|(column, values)| (table.name.clone(), column, values)),
+        ),
+        (Some(table), keyword) => {
+          if let Ok(value_type) = RailsColumn::from_str(keyword) {
+            table
+              .columns
+              .push(ColumnData::from_arguments(value_type, arguments));
+          }
+        }
+      }
     }
 
-    if inside_table_expression {
+    if table.is_some() {
       panic!(
         "{}",
         "Parsing Error: \n create_table block does not have matching end"
       )
     }
-    // We can only get here when there are no create_table tokens and
-    // we've reached the last token in our list.
-    (None, tokens.split_off(1))
-  }
-}
 
-impl Database {
-  pub fn from(schema: &str) -> Self {
-    let mut tokens: Vec<&str> = schema.split_whitespace().collect();
-    let mut database: Database = Database(vec![]);
-    loop {
-      let (table, remaining_tokens) = Table::from_tokens(tokens);
-      if let Some(table) = table {
-        database.0.push(table);
+    for (table, column, foreign_key) in foreign_keys {
+      let column = database
+        .0
+        .iter_mut()
+        .filter(|candidate| candidate.name == table)
+        .flat_map(|table| table.columns.iter_mut())
+        .find(|candidate| candidate.name == column);
+      if let Some(column) = column {
+        column.options.foreign_key = Some(foreign_key);
+      }
+    }
+
+    for (name, index) in indexes {
+      if let Some(table) = database.0.iter_mut().find(|table| table.name == name) {
+        table.indexes.push(index);
+      }
+    }
+
+    for (table, column, values) in checks {
+      let column = database
+        .0
+        .iter_mut()
+        .filter(|candidate| candidate.name == table)
+        .flat_map(|table| table.columns.iter_mut())
+        .find(|candidate| candidate.name == column);
+      if let Some(column) = column {
+        column.options.allowed_values = values;
       }
-      if remaining_tokens.is_empty() {
-        break;
-      } else {
-        tokens = remaining_tokens;
+    }
+
+    // Enums are usually created before the tables that use them, but their
+    // values are only needed once everything has been read
+    let enum_types = database
+      .0
+      .iter_mut()
+      .flat_map(|table| table.columns.iter_mut())
+      .filter_map(|column| column.options.enum_type.as_mut());
+    for enum_type in enum_types {
+      if let Some(declared) = enums
+        .iter()
+        .find(|declared: &&EnumType| declared.name == enum_type.name)
+      {
+        enum_type.values = declared.values.clone();
       }
     }
 
@@ -109,7 +459,10 @@ impl Database {
 mod tests {
   use std::{panic, str::FromStr, vec};
 
-  use crate::{ColumnData, Database, RailsColumn, Table};
+  use crate::{
+    ColumnData, ColumnOptions, Database, DefaultValue, EnumType, ForeignKey, Index, RailsColumn,
+    Table,
+  };
   #[test]
   fn rails_columns_respond_to_from_str() {
     assert_eq!(
@@ -118,6 +471,15 @@ mod tests {
     )
   }
 
+  #[test]
+  fn json_and_jsonb_columns_are_json_not_hstore() {
+    assert_eq!(RailsColumn::from_str("t.json").unwrap(), RailsColumn::JsonB);
+    assert_eq!(
+      RailsColumn::from_str("t.jsonb").unwrap(),
+      RailsColumn::JsonB
+    );
+  }
+
   #[test]
   fn rails_columns_throws_an_error_when_it_doesnt_recognize_the_token() {
     assert!(RailsColumn::from_str("unrecognized").is_err())
@@ -134,8 +496,10 @@ mod tests {
         columns: vec![ColumnData {
           name: "a".to_string(),
           value_type: RailsColumn::PrimaryKey,
-          nullable: true
-        }]
+          nullable: true,
+          options: ColumnOptions::default()
+        }],
+        indexes: vec![],
       }])
     );
   }
@@ -156,16 +520,20 @@ mod tests {
           columns: vec![ColumnData {
             name: "a".to_string(),
             value_type: RailsColumn::PrimaryKey,
-            nullable: true
-          }]
+            nullable: true,
+            options: ColumnOptions::default()
+          }],
+          indexes: vec![],
         },
         Table {
           name: "table_2".to_string(),
           columns: vec![ColumnData {
             name: "b".to_string(),
             value_type: RailsColumn::PrimaryKey,
-            nullable: true
-          }]
+            nullable: true,
+            options: ColumnOptions::default()
+          }],
+          indexes: vec![],
         }
       ])
     );
@@ -186,7 +554,7 @@ mod tests {
           message.as_ref(),
           "Warning Invalid Rails Schema:\n Found create_table inside create_table block"
         ),
-        Err(_) => assert!(false),
+        Err(_) => panic!("expected a panic message"),
       }
     }
   }
@@ -222,90 +590,355 @@ end";
           ColumnData {
             value_type: RailsColumn::PrimaryKey,
             name: "a".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::String,
             name: "b".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Text,
             name: "c".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Integer,
             name: "d".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Bigint,
             name: "e".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Float,
             name: "f".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Decimal,
             name: "g".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Numeric,
             name: "h".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Datetime,
             name: "i".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Time,
             name: "j".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Date,
             name: "k".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Binary,
             name: "l".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Boolean,
             name: "m".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::HStore,
             name: "n".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
-            value_type: RailsColumn::HStore,
+            value_type: RailsColumn::JsonB,
             name: "o".to_string(),
-            nullable: true
+            nullable: true,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Datetime,
             name: "created_at".to_string(),
-            nullable: false
+            nullable: false,
+            options: ColumnOptions::default()
           },
           ColumnData {
             value_type: RailsColumn::Datetime,
             name: "updated_at".to_string(),
-            nullable: false
+            nullable: false,
+            options: ColumnOptions::default()
           }
-        ]
+        ],
+        indexes: vec![],
       }])
     );
   }
+
+  #[test]
+  fn it_reads_the_options_of_a_column() {
+    let table = "create_table \"accounts\", id: false, force: :cascade do |t|
+    t.integer \"logins\", limit: 2, null: false # how many, times
+    t.decimal \"balance\", precision: 10, scale: 2, comment: \"in, dollars\"
+  end";
+    assert_eq!(
+      Database::from(table),
+      Database(vec![Table {
+        name: "accounts".to_string(),
+        columns: vec![
+          ColumnData {
+            value_type: RailsColumn::Integer,
+            name: "logins".to_string(),
+            nullable: false,
+            options: ColumnOptions {
+              limit: Some(2),
+              ..ColumnOptions::default()
+            }
+          },
+          ColumnData {
+            value_type: RailsColumn::Decimal,
+            name: "balance".to_string(),
+            nullable: true,
+            options: ColumnOptions {
+              precision: Some(10),
+              scale: Some(2),
+              ..ColumnOptions::default()
+            }
+          }
+        ],
+        indexes: vec![],
+      }])
+    );
+  }
+
+  #[test]
+  fn it_reads_literal_and_expression_defaults() {
+    let table = "create_table \"posts\", id: false, force: :cascade do |t|
+    t.string \"status\", default: \"draft\", null: false
+    t.integer \"views\", default: 0
+    t.boolean \"published\", default: false
+    t.jsonb \"settings\", default: {}
+    t.datetime \"published_at\", default: -> { \"now()\" }
+    t.string \"title\", default: nil
+  end";
+    let defaults = Database::from(table).0[0]
+      .columns
+      .iter()
+      .map(|column| column.options.default.clone())
+      .collect::<Vec<Option<DefaultValue>>>();
+    assert_eq!(
+      defaults,
+      vec![
+        Some(DefaultValue::String("draft".to_string())),
+        Some(DefaultValue::Integer(0)),
+        Some(DefaultValue::Boolean(false)),
+        Some(DefaultValue::Hash(vec![])),
+        Some(DefaultValue::Expression("now()".to_string())),
+        None
+      ]
+    );
+  }
+
+  #[test]
+  fn it_adds_the_primary_key_rails_creates_implicitly() {
+    let table = "create_table \"users\", force: :cascade do |t|
+    t.string \"name\"
+  end
+  create_table \"tokens\", id: :uuid, default: -> { \"gen_random_uuid()\" } do |t|
+  end
+  create_table \"tags\", id: false do |t|
+  end";
+    assert_eq!(
+      Database::from(table),
+      Database(vec![
+        Table {
+          name: "users".to_string(),
+          columns: vec![
+            ColumnData {
+              value_type: RailsColumn::PrimaryKey,
+              name: "id".to_string(),
+              nullable: false,
+              options: ColumnOptions {
+                primary_key: true,
+                ..ColumnOptions::default()
+              }
+            },
+            ColumnData {
+              value_type: RailsColumn::String,
+              name: "name".to_string(),
+              nullable: true,
+              options: ColumnOptions::default()
+            }
+          ],
+          indexes: vec![],
+        },
+        Table {
+          name: "tokens".to_string(),
+          columns: vec![ColumnData {
+            value_type: RailsColumn::Uuid,
+            name: "id".to_string(),
+            nullable: false,
+            options: ColumnOptions {
+              primary_key: true,
+              default: Some(DefaultValue::Expression("gen_random_uuid()".to_string())),
+              ..ColumnOptions::default()
+            }
+          }],
+          indexes: vec![],
+        },
+        Table {
+          name: "tags".to_string(),
+          columns: vec![],
+          indexes: vec![],
+        }
+      ])
+    );
+  }
+
+  #[test]
+  fn it_attaches_foreign_keys_to_their_columns() {
+    let schema = "create_table \"categories\", force: :cascade do |t|
+  end
+  create_table \"posts\", force: :cascade do |t|
+    t.bigint \"category_id\"
+    t.bigint \"author_id\"
+  end
+  add_foreign_key \"posts\", \"categories\"
+  add_foreign_key \"posts\", \"users\", column: \"author_id\", on_delete: :cascade";
+    let foreign_keys = Database::from(schema).0[1]
+      .columns
+      .iter()
+      .map(|column| column.options.foreign_key.clone())
+      .collect::<Vec<Option<ForeignKey>>>();
+    assert_eq!(
+      foreign_keys,
+      vec![
+        None,
+        Some(ForeignKey {
+          table: "categories".to_string(),
+          primary_key: "id".to_string()
+        }),
+        Some(ForeignKey {
+          table: "users".to_string(),
+          primary_key: "id".to_string()
+        })
+      ]
+    );
+  }
+
+  #[test]
+  fn it_reads_enum_types_and_array_columns() {
+    let schema = "create_enum \"mood\", [\"happy\", \"sad\"]
+  create_table \"people\", id: false, force: :cascade do |t|
+    t.enum \"current_mood\", enum_type: \"mood\", null: false
+    t.string \"tags\", array: true
+  end";
+    assert_eq!(
+      Database::from(schema),
+      Database(vec![Table {
+        name: "people".to_string(),
+        columns: vec![
+          ColumnData {
+            value_type: RailsColumn::Enum,
+            name: "current_mood".to_string(),
+            nullable: false,
+            options: ColumnOptions {
+              enum_type: Some(EnumType {
+                name: "mood".to_string(),
+                values: vec!["happy".to_string(), "sad".to_string()]
+              }),
+              ..ColumnOptions::default()
+            }
+          },
+          ColumnData {
+            value_type: RailsColumn::String,
+            name: "tags".to_string(),
+            nullable: true,
+            options: ColumnOptions {
+              array: true,
+              ..ColumnOptions::default()
+            }
+          }
+        ],
+        indexes: vec![],
+      }])
+    );
+  }
+
+  #[test]
+  fn it_reads_the_values_a_check_constraint_allows() {
+    let schema = "create_table \"posts\", id: false, force: :cascade do |t|
+    t.string \"status\", null: false
+    t.string \"kind\"
+    t.integer \"rating\"
+    t.check_constraint \"status IN ('draft', 'it''s live')\", name: \"status_check\"
+    t.check_constraint \"rating > 0 AND rating IN (1, 2)\", name: \"rating_check\"
+  end
+  add_check_constraint \"posts\", \"((kind)::text = ANY ((ARRAY['note'::character varying, 'link'::character varying])::text[]))\", name: \"kind_check\"";
+    let database = Database::from(schema);
+    let allowed = database.0[0]
+      .columns
+      .iter()
+      .map(|column| column.options.allowed_values.clone())
+      .collect::<Vec<Vec<String>>>();
+    assert_eq!(
+      allowed,
+      vec![
+        vec!["draft".to_string(), "it's live".to_string()],
+        vec!["note".to_string(), "link".to_string()],
+        vec![],
+      ]
+    );
+  }
+
+  #[test]
+  fn it_reads_indexes_inside_and_after_a_table() {
+    let schema = "create_table \"posts\", force: :cascade do |t|
+    t.bigint \"user_id\"
+    t.string \"slug\"
+    t.index [\"user_id\", \"slug\"], name: \"index_posts_on_user_id_and_slug\", unique: true
+    t.index \"slug\"
+  end
+  add_index \"posts\", [\"user_id\"], name: \"index_posts_on_user_id\"";
+    assert_eq!(
+      Database::from(schema).0[0].indexes,
+      vec![
+        Index {
+          name: Some("index_posts_on_user_id_and_slug".to_string()),
+          columns: vec!["user_id".to_string(), "slug".to_string()],
+          unique: true,
+        },
+        Index {
+          name: None,
+          columns: vec!["slug".to_string()],
+          unique: false,
+        },
+        Index {
+          name: Some("index_posts_on_user_id".to_string()),
+          columns: vec!["user_id".to_string()],
+          unique: false,
+        },
+      ]
+    );
+  }
 }
@@ -0,0 +1,267 @@
+use std::{collections::HashMap, fmt};
+
+use serde::Deserialize;
+
+use super::{config::Config, Database, ForeignKey, Table};
+
+// Settings for working out foreign keys Rails never declared, from columns
+// named `<singular table>_id`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RelationshipConfig {
+  pub infer: bool,
+  // Maps "table.column" to the table it references. An empty string marks
+  // the column as not being a reference at all.
+  pub overrides: HashMap<String, String>,
+}
+
+// A column that looks like a reference but couldn't be linked to a table
+#[derive(Debug, PartialEq)]
+pub enum InferenceWarning {
+  // More than one table fits the name, or none can because it's polymorphic
+  Ambiguous {
+    table: String,
+    column: String,
+    candidates: Vec<String>,
+  },
+  // Nothing in the schema fits the name
+  Dangling {
+    table: String,
+    column: String,
+  },
+}
+
+impl fmt::Display for InferenceWarning {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      InferenceWarning::Ambiguous {
+        table,
+        column,
+        candidates,
+      } if candidates.is_empty() => write!(
+        f,
+        "{}.{} is polymorphic, so it can't be linked to a single table",
+        table, column
+      ),
+      InferenceWarning::Ambiguous {
+        table,
+        column,
+        candidates,
+      } => write!(
+        f,
+        "{}.{} could reference any of {}",
+        table,
+        column,
+        candidates.join(", ")
+      ),
+      InferenceWarning::Dangling { table, column } => {
+        write!(f, "{}.{} doesn't match any table", table, column)
+      }
+    }
+  }
+}
+
+impl Table {
+  fn primary_key_name(&self) -> String {
+    self
+      .columns
+      .iter()
+      .find(|column| column.is_primary_key())
+      .map_or_else(|| "id".to_string(), |column| column.name.clone())
+  }
+}
+
+impl Database {
+  // Works out which table a column refers to from an override or its name
+  fn infer_reference(
+    &self,
+    table: &Table,
+    column: &str,
+    config: &Config,
+  ) -> Option<Result<ForeignKey, InferenceWarning>> {
+    let warning = |candidates: Vec<String>| InferenceWarning::Ambiguous {
+      table: table.name.clone(),
+      column: column.to_string(),
+      candidates,
+    };
+    let dangling = || InferenceWarning::Dangling {
+      table: table.name.clone(),
+      column: column.to_string(),
+    };
+
+    let candidates = match config
+      .relationships
+      .overrides
+      .get(&format!("{}.{}", table.name, column))
+    {
+      Some(target) if target.is_empty() => return None,
+      Some(target) => vec![target.clone()],
+      None => {
+        let stem = column.strip_suffix("_id")?;
+        // Polymorphic references keep the name of their table in `<stem>_type`
+        let polymorphic = format!("{}_type", stem);
+        if table
+          .columns
+          .iter()
+          .any(|column| column.name == polymorphic)
+        {
+          return Some(Err(warning(vec![])));
+        }
+        // Some schemas don't pluralise their table names
+        let mut candidates = vec![config.inflections.pluralize(stem), stem.to_string()];
+        candidates.dedup();
+        candidates
+      }
+    };
+
+    let mut targets = candidates.iter().filter_map(|name| self.table(name));
+    match (targets.next(), targets.next()) {
+      (Some(target), None) => Some(Ok(ForeignKey {
+        table: target.name.clone(),
+        primary_key: target.primary_key_name(),
+      })),
+      (Some(_), Some(_)) => Some(Err(warning(candidates))),
+      (None, _) => Some(Err(dangling())),
+    }
+  }
+
+  // Links `<singular>_id` columns that have no declared foreign key to the
+  // table they're named after, returning the columns that couldn't be linked
+  pub fn infer_foreign_keys(&mut self, config: &Config) -> Vec<InferenceWarning> {
+    let mut inferred = vec![];
+    let mut warnings = vec![];
+    for (table_index, table) in self.0.iter().enumerate() {
+      for (column_index, column) in table.columns.iter().enumerate() {
+        if column.options.foreign_key.is_some() || column.is_primary_key() {
+          continue;
+        }
+        match self.infer_reference(table, &column.name, config) {
+          Some(Ok(foreign_key)) => inferred.push((table_index, column_index, foreign_key)),
+          Some(Err(warning)) => warnings.push(warning),
+          None => (),
+        }
+      }
+    }
+
+    for (table, column, foreign_key) in inferred {
+      self.0[table].columns[column].options.foreign_key = Some(foreign_key);
+    }
+    warnings
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::InferenceWarning;
+  use crate::{config::Config, Database, ForeignKey};
+
+  fn foreign_keys(database: &Database, table: usize) -> Vec<Option<ForeignKey>> {
+    database.0[table]
+      .columns
+      .iter()
+      .map(|column| column.options.foreign_key.clone())
+      .collect()
+  }
+
+  #[test]
+  fn it_links_columns_to_the_pluralised_table() {
+    let schema = "create_table \"people\", force: :cascade do |t|
+  end
+  create_table \"categories\", id: :uuid, primary_key: \"uuid\", force: :cascade do |t|
+  end
+  create_table \"posts\", force: :cascade do |t|
+    t.bigint \"person_id\"
+    t.uuid \"category_id\"
+    t.string \"title\"
+  end";
+    let mut database = Database::from(schema);
+    let warnings = database.infer_foreign_keys(&Config::default());
+    assert_eq!(warnings, vec![]);
+    assert_eq!(
+      foreign_keys(&database, 2),
+      vec![
+        None,
+        Some(ForeignKey {
+          table: "people".to_string(),
+          primary_key: "id".to_string()
+        }),
+        Some(ForeignKey {
+          table: "categories".to_string(),
+          primary_key: "uuid".to_string()
+        }),
+        None
+      ]
+    )
+  }
+
+  #[test]
+  fn it_reports_references_it_cannot_link() {
+    let schema = "create_table \"account\", force: :cascade do |t|
+  end
+  create_table \"accounts\", force: :cascade do |t|
+  end
+  create_table \"comments\", force: :cascade do |t|
+    t.bigint \"account_id\"
+    t.bigint \"parent_id\"
+    t.bigint \"commentable_id\"
+    t.string \"commentable_type\"
+  end";
+    let mut database = Database::from(schema);
+    let warnings = database.infer_foreign_keys(&Config::default());
+    assert_eq!(
+      warnings,
+      vec![
+        InferenceWarning::Ambiguous {
+          table: "comments".to_string(),
+          column: "account_id".to_string(),
+          candidates: vec!["accounts".to_string(), "account".to_string()]
+        },
+        InferenceWarning::Dangling {
+          table: "comments".to_string(),
+          column: "parent_id".to_string()
+        },
+        InferenceWarning::Ambiguous {
+          table: "comments".to_string(),
+          column: "commentable_id".to_string(),
+          candidates: vec![]
+        }
+      ]
+    );
+    assert_eq!(
+      foreign_keys(&database, 2),
+      vec![None, None, None, None, None]
+    );
+  }
+
+  #[test]
+  fn overrides_take_precedence_over_the_column_name() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+  end
+  create_table \"posts\", force: :cascade do |t|
+    t.bigint \"author_id\"
+    t.bigint \"user_id\"
+  end";
+    let config: Config = toml::from_str(
+      "[relationships]
+infer = true
+
+[relationships.overrides]
+\"posts.author_id\" = \"users\"
+\"posts.user_id\" = \"\"",
+    )
+    .unwrap();
+    let mut database = Database::from(schema);
+    assert_eq!(database.infer_foreign_keys(&config), vec![]);
+    assert_eq!(
+      foreign_keys(&database, 1),
+      vec![
+        None,
+        Some(ForeignKey {
+          table: "users".to_string(),
+          primary_key: "id".to_string()
+        }),
+        None
+      ]
+    )
+  }
+}
@@ -1,78 +1,1373 @@
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  sync::LazyLock,
+};
+
+use regex::Regex;
+use serde::Deserialize;
+
 use crate::ColumnData;
 
+use super::case::{is_identifier, Case};
+use super::config::{Adapter, Config, VariantConfig};
+use super::Association;
+use super::AssociationKind;
 use super::Database;
+use super::DefaultValue;
+use super::EnumType;
+use super::NumericType;
 use super::RailsColumn;
 use super::Table;
+use super::Variant;
+
+// How decimal columns are represented in Rust
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RustDecimal {
+  #[default]
+  RustDecimal,
+  BigDecimal,
+  F64,
+  String,
+}
+
+// How datetime, date and time columns are represented in Rust
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RustDateTime {
+  #[default]
+  String,
+  Chrono,
+  Time,
+}
+
+// The library the structs are read from the database with, if any
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RustFlavour {
+  #[default]
+  Plain,
+  Sqlx,
+}
+
+// Whether tables are written one after another or each in a module of its own
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RustLayout {
+  #[default]
+  Flat,
+  Modules,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RustConfig {
+  // Written before each struct, field and module, e.g. "pub" or "pub(crate)"
+  pub visibility: String,
+  pub derives: Vec<String>,
+  // Any other attributes for each struct, e.g. "#[serde(deny_unknown_fields)]"
+  pub attributes: Vec<String>,
+  pub layout: RustLayout,
+  pub decimal: RustDecimal,
+  pub date_time: RustDateTime,
+  // Emit an `impl Default` for tables with column defaults
+  pub defaults: bool,
+  pub variants: VariantConfig,
+  // Add fields for eager loaded belongs_to and has_many associations
+  pub associations: bool,
+  // The case of struct fields. Any field whose name no longer matches its
+  // column gets a `#[serde(rename)]` back to it.
+  pub field_case: Case,
+  // Serialise every field in another case, with `#[serde(rename_all)]`
+  pub rename_all: Option<Case>,
+  pub flavour: RustFlavour,
+  // With the sqlx flavour, add find_by_id, insert, update and delete
+  // functions to each table
+  pub crud: bool,
+  // Give each table's primary key a newtype like `UserId`, used for it and
+  // every foreign key pointing at it
+  pub id_types: bool,
+}
+
+impl Default for RustConfig {
+  fn default() -> Self {
+    RustConfig {
+      visibility: "pub".to_string(),
+      derives: [
+        "Debug",
+        "Clone",
+        "PartialEq",
+        "serde::Serialize",
+        "serde::Deserialize",
+      ]
+      .iter()
+      .map(|derive| derive.to_string())
+      .collect(),
+      attributes: vec![],
+      layout: RustLayout::default(),
+      decimal: RustDecimal::default(),
+      date_time: RustDateTime::default(),
+      defaults: true,
+      variants: VariantConfig::default(),
+      associations: false,
+      field_case: Case::Snake,
+      rename_all: None,
+      flavour: RustFlavour::default(),
+      crud: false,
+      id_types: false,
+    }
+  }
+}
 
 pub trait ToRust {
-  fn to_rust(&self) -> String;
+  fn to_rust(&self, config: &Config) -> String;
 }
 
 impl ToRust for Database {
-  fn to_rust(&self) -> String {
-    self.0.iter().fold(String::new(), |specs, table| {
-      if specs.is_empty() {
-        [specs, table.to_rust()].join("")
+  fn to_rust(&self, config: &Config) -> String {
+    // sqlx reads Postgres enums into Rust ones, the others use strings
+    let enums = match config.rust.flavour {
+      RustFlavour::Plain => vec![],
+      RustFlavour::Sqlx => self
+        .enums()
+        .into_iter()
+        .filter(|enum_type| !enum_type.values.is_empty())
+        .collect(),
+    };
+    let id_names = self
+      .0
+      .iter()
+      .filter(|table| config.rust.id_types && table.primary_key().is_some())
+      .map(|table| id_name(table, config));
+    let type_names = self
+      .0
+      .iter()
+      .map(|table| type_name(table, config))
+      .chain(id_names)
+      .chain(enums.iter().map(|enum_type| enum_name(&enum_type.name)))
+      .collect::<Vec<String>>();
+    let enums = enums.into_iter().map(|enum_type| {
+      enum_declaration(
+        enum_type,
+        &["sqlx::Type"],
+        &[format!(
+          "#[sqlx(type_name = {:?}, rename_all = \"snake_case\")]",
+          enum_type.name
+        )],
+        &[("serde", "rename"), ("sqlx", "rename")],
+        config,
+      )
+    });
+    let tables = self.0.iter().map(|table| {
+      let associations = if config.rust.associations {
+        self.associations(table)
       } else {
-        [specs, table.to_rust()].join("\n\n")
-      }
+        vec![]
+      };
+      let keys = if config.rust.id_types {
+        self.keys(table)
+      } else {
+        vec![]
+      };
+      table_to_rust(table, &associations, &keys, config)
+    });
+    match config.rust.layout {
+      RustLayout::Flat => with_imports(
+        &time_formats(&enums.chain(tables).collect::<Vec<String>>().join("\n\n")),
+        &type_names,
+      ),
+      RustLayout::Modules => [with_imports(
+        &enums.collect::<Vec<String>>().join("\n\n"),
+        &type_names,
+      )]
+      .into_iter()
+      .chain(self.0.iter().zip(tables).map(|(table, spec)| {
+        module(
+          &module_name(table),
+          &with_imports(&time_formats(&spec), &type_names),
+          config,
+        )
+      }))
+      .filter(|item| !item.is_empty())
+      .collect::<Vec<String>>()
+      .join("\n\n"),
+    }
+  }
+}
+
+// The paths a generated struct can use, shortened with a `use` at the top
+static PATHS: LazyLock<Regex> = LazyLock::new(|| {
+  Regex::new(
+    r"\b(sqlx::types::chrono|sqlx::types::time|sqlx::types|sqlx::postgres::types|std::collections|bigdecimal|chrono|rust_decimal|serde_json|serde|time|uuid)::(\w+)",
+  )
+  .expect("the paths pattern is valid")
+});
+
+// Replaces each crate path with its last segment and a `use` for it, unless
+// that would clash with one of the generated types or another import
+pub(crate) fn with_imports(spec: &str, type_names: &[String]) -> String {
+  let mut imports: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+  let mut shortened = String::new();
+  let mut last = 0;
+  for captures in PATHS.captures_iter(spec) {
+    let (path, krate, item) = match (captures.get(0), captures.get(1), captures.get(2)) {
+      (Some(path), Some(krate), Some(item)) => (path, krate.as_str(), item.as_str()),
+      _ => continue,
+    };
+    let clashes = type_names.iter().any(|name| name == item)
+      || imports
+        .iter()
+        .any(|(other, items)| *other != krate && items.contains(item));
+    // Paths like super::time::Time belong to a generated module
+    if clashes || spec[..path.start()].ends_with("::") {
+      continue;
+    }
+    imports.entry(krate).or_default().insert(item);
+    shortened.push_str(&spec[last..path.start()]);
+    shortened.push_str(item);
+    last = path.end();
+  }
+  shortened.push_str(&spec[last..]);
+
+  // Functions and macros are listed before types, the way rustfmt sorts them
+  let use_line = |(krate, items): (&&str, &BTreeSet<&str>)| {
+    let mut items = items.iter().copied().collect::<Vec<&str>>();
+    items.sort_by_key(|item| (item.starts_with(char::is_uppercase), *item));
+    match items.as_slice() {
+      [item] => format!("use {}::{};", krate, item),
+      items => format!("use {}::{{{}}};", krate, items.join(", ")),
+    }
+  };
+  let (std, external): (Vec<_>, Vec<_>) = imports
+    .iter()
+    .partition(|(krate, _)| krate.starts_with("std::"));
+  [std, external]
+    .into_iter()
+    .map(|group| {
+      group
+        .into_iter()
+        .map(use_line)
+        .collect::<Vec<String>>()
+        .join("\n")
     })
+    .chain([shortened])
+    .filter(|section| !section.is_empty())
+    .collect::<Vec<String>>()
+    .join("\n\n")
+}
+
+pub(crate) fn visibility(config: &Config) -> String {
+  match config.rust.visibility.as_str() {
+    "" => String::new(),
+    visibility => format!("{} ", visibility),
+  }
+}
+
+// Strict and reserved keywords from the reference, any of which would stop
+// the struct from compiling
+const KEYWORDS: &[&str] = &[
+  "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+  "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+  "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+  "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+  "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+// Keywords are written as raw identifiers, except the few that can't be.
+// `_` can't name anything either, and `__` has to stay apart from what `_`
+// becomes, so names of nothing but underscores get one more.
+pub(crate) fn escape(name: String) -> String {
+  match name.as_str() {
+    "crate" | "self" | "Self" | "super" => format!("{}_", name),
+    keyword if KEYWORDS.contains(&keyword) => format!("r#{}", name),
+    _ if name.chars().all(|character| character == '_') => format!("{}_", name),
+    _ if !is_identifier(&name) => format!("_{}", name),
+    _ => name,
+  }
+}
+
+// Puts a name in `case` and escapes the result. Names with no words to
+// convert, like `_` or `__`, are escaped as they were written instead.
+pub(crate) fn identifier(name: &str, case: Case, acronyms: &[String]) -> String {
+  match case.convert(name, acronyms) {
+    converted if converted.is_empty() => escape(name.to_string()),
+    converted => escape(converted),
+  }
+}
+
+pub(crate) fn field_name(name: &str, config: &Config) -> String {
+  identifier(name, config.rust.field_case, &config.acronyms)
+}
+
+// What serde calls each case in `rename_all`
+fn serde_case(case: Case) -> &'static str {
+  match case {
+    Case::Snake => "snake_case",
+    Case::Camel => "camelCase",
+    Case::Pascal => "PascalCase",
+    Case::Kebab => "kebab-case",
+    Case::ScreamingSnake => "SCREAMING_SNAKE_CASE",
   }
 }
-fn format_name(name: &str) -> String {
-  name
-    .split('_')
-    .map(|n| n.chars().next().unwrap().to_uppercase().to_string() + &n[1..])
+
+// A struct field, renamed back to its column when serde wouldn't otherwise
+// serialise it under the same name Rails does
+pub(crate) fn field(column_name: &str, value_type: &str, config: &Config) -> String {
+  field_with(column_name, value_type, &[], config)
+}
+
+// A struct field with more for serde to know about it, like how to read it
+fn field_with(column_name: &str, value_type: &str, serde: &[String], config: &Config) -> String {
+  field_named(
+    &field_name(column_name, config),
+    column_name,
+    value_type,
+    serde,
+    config,
+  )
+}
+
+// A struct field under a name of the caller's choosing
+pub(crate) fn field_named(
+  name: &str,
+  column_name: &str,
+  value_type: &str,
+  serde: &[String],
+  config: &Config,
+) -> String {
+  let identifier = name.strip_prefix("r#").unwrap_or(name);
+  let (serialized, expected) = match config.rust.rename_all {
+    Some(case) => (
+      case.convert(identifier, &[]),
+      case.convert(column_name, &[]),
+    ),
+    None => (identifier.to_string(), column_name.to_string()),
+  };
+  let mut serde = serde.to_vec();
+  if serialized != expected {
+    serde.insert(0, format!("rename = {:?}", expected));
+  }
+  let visibility = visibility(config);
+  if serde.is_empty() {
+    format!("{}{}: {},", visibility, name, value_type)
+  } else {
+    format!(
+      "#[serde({})]\n   {}{}: {},",
+      serde.join(", "),
+      visibility,
+      name,
+      value_type
+    )
+  }
+}
+
+// The module serde reads and writes a time crate type with. Its own format
+// isn't the ISO 8601 Rails sends, so datetimes use RFC 3339 and dates and
+// times a format declared alongside the structs.
+fn time_format(value_type: &str) -> Option<&'static str> {
+  match value_type {
+    "time::OffsetDateTime" => Some("::time::serde::rfc3339"),
+    "time::Date" => Some("date_format"),
+    "time::Time" => Some("time_format"),
+    _ => None,
+  }
+}
+
+// The module a patch field holding a time crate type is read and written
+// with, which wraps the type's format so `null` is kept apart from a missing field
+fn patch_format(format: &str) -> String {
+  format!("{}_patch", format.rsplit("::").next().unwrap_or(format))
+}
+
+// What a patch format module reads and writes through `format`'s own option module
+fn patch_format_declaration(format: &str, value_type: &str) -> String {
+  format!(
+    "mod {name} {{
+    pub fn serialize<S: ::serde::Serializer>(
+        value: &Option<Option<{value_type}>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {{
+        {format}::option::serialize(&value.flatten(), serializer)
+    }}
+
+    pub fn deserialize<'de, D: ::serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Option<{value_type}>>, D::Error> {{
+        {format}::option::deserialize(deserializer).map(Some)
+    }}
+}}",
+    name = patch_format(format),
+    value_type = value_type,
+    // The formats declared alongside the structs are a level up from here
+    format = if format.starts_with("::") {
+      format.to_string()
+    } else {
+      format!("super::{}", format)
+    },
+  )
+}
+
+// Declares the formats and helpers the fields in `spec` are read with
+fn time_formats(spec: &str) -> String {
+  let formats = [
+    (
+      "date_format".to_string(),
+      "::time::serde::format_description!(date_format, time::Date, \"[year]-[month]-[day]\");"
+        .to_string(),
+    ),
+    (
+      "time_format".to_string(),
+      "::time::serde::format_description!(\n    time_format,\n    time::Time,\n    \"[hour]:[minute]:[second][optional [.[subsecond]]]\"\n);"
+        .to_string(),
+    ),
+    (
+      patch_format("::time::serde::rfc3339"),
+      patch_format_declaration("::time::serde::rfc3339", "::time::OffsetDateTime"),
+    ),
+    (
+      patch_format("date_format"),
+      patch_format_declaration("date_format", "::time::Date"),
+    ),
+    (
+      patch_format("time_format"),
+      patch_format_declaration("time_format", "::time::Time"),
+    ),
+    (
+      "double_option".to_string(),
+      "// Reads a field that is there as `Some`, so a `null` is `Some(None)`
+fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: ::serde::Deserialize<'de>,
+    D: ::serde::Deserializer<'de>,
+{
+    ::serde::Deserialize::deserialize(deserializer).map(Some)
+}"
+      .to_string(),
+    ),
+  ];
+  let declarations = formats
+    .iter()
+    .filter(|(name, _)| spec.contains(&format!("with = \"{}", name)))
+    .map(|(_, declaration)| declaration.as_str())
+    .collect::<Vec<&str>>();
+  if declarations.is_empty() {
+    spec.to_string()
+  } else {
+    format!("{}\n\n{}", declarations.join("\n\n"), spec)
+  }
+}
+
+// The struct declaration along with its derives and attributes
+fn declaration(name: &str, fields: &str, config: &Config) -> String {
+  declaration_with(name, fields, &[], &[], config)
+}
+
+// A declaration that other formats can add their own derives and attributes to
+pub(crate) fn declaration_with(
+  name: &str,
+  fields: &str,
+  derives: &[&str],
+  attributes: &[String],
+  config: &Config,
+) -> String {
+  let mut lines = vec![];
+  let derives = config
+    .rust
+    .derives
+    .iter()
+    .map(String::as_str)
+    .chain(derives.iter().copied())
+    .collect::<Vec<&str>>();
+  if !derives.is_empty() {
+    lines.push(format!("#[derive({})]", derives.join(", ")));
+  }
+  lines.extend(config.rust.attributes.iter().cloned());
+  lines.extend(attributes.iter().cloned());
+  if let Some(case) = config.rust.rename_all {
+    lines.push(format!("#[serde(rename_all = {:?})]", serde_case(case)));
+  }
+  lines.push(format!(
+    "{}struct {} {{{}\n}}",
+    visibility(config),
+    name,
+    fields
+  ));
+  lines.join("\n")
+}
+
+// The type for a Postgres enum, or any other type the database defines
+pub(crate) fn enum_name(name: &str) -> String {
+  identifier(name, Case::Pascal, &[])
+}
+
+// Each of the enum's variants, paired with the label the database knows it by
+pub(crate) fn enum_variants<'a>(
+  enum_type: &'a EnumType,
+  config: &Config,
+) -> Vec<(String, &'a String)> {
+  enum_type
+    .values
+    .iter()
+    .map(|value| (identifier(value, Case::Pascal, &config.acronyms), value))
+    .collect()
+}
+
+// An enum serialised under its labels. Variants whose label isn't their
+// name in snake_case are renamed with each attribute and key in `renamed_by`,
+// e.g. `("serde", "rename")` for `#[serde(rename = "label")]`.
+pub(crate) fn enum_declaration(
+  enum_type: &EnumType,
+  derives: &[&str],
+  attributes: &[String],
+  renamed_by: &[(&str, &str)],
+  config: &Config,
+) -> String {
+  let variants = enum_variants(enum_type, config)
+    .into_iter()
+    .map(|(variant, value)| {
+      let renames = renamed_by
+        .iter()
+        .filter(|_| Case::Snake.convert(&variant, &[]) != *value)
+        .map(|(attribute, key)| format!("    #[{}({} = {:?})]\n", attribute, key, value))
+        .collect::<String>();
+      format!("{}    {},", renames, variant)
+    })
+    .collect::<Vec<String>>()
+    .join("\n");
+  let derives = config
+    .rust
+    .derives
+    .iter()
+    .map(String::as_str)
+    .chain(derives.iter().copied())
+    .collect::<Vec<&str>>();
+  let mut lines = vec![];
+  if !derives.is_empty() {
+    lines.push(format!("#[derive({})]", derives.join(", ")));
+  }
+  lines.extend(attributes.iter().cloned());
+  lines.push("#[serde(rename_all = \"snake_case\")]".to_string());
+  lines.push(format!(
+    "{}enum {} {{\n{}\n}}",
+    visibility(config),
+    enum_name(&enum_type.name),
+    variants
+  ));
+  lines.join("\n")
+}
+
+pub(crate) fn type_name(table: &Table, config: &Config) -> String {
+  escape(table.type_name(config))
+}
+
+// Wraps `body` in a module, indented the way rustfmt would
+pub(crate) fn module(name: &str, body: &str, config: &Config) -> String {
+  let body = body
+    .lines()
+    .map(|line| match line {
+      "" => String::new(),
+      line => format!("    {}", line),
+    })
     .collect::<Vec<String>>()
-    .join("")
+    .join("\n");
+  format!("{}mod {} {{\n{}\n}}", visibility(config), name, body)
+}
+
+pub(crate) fn module_name(table: &Table) -> String {
+  identifier(&table.name, Case::Snake, &[])
+}
+
+// Another table's type, which lives in a sibling module when each table has its own
+fn type_path(table: &Table, config: &Config) -> String {
+  match config.rust.layout {
+    RustLayout::Flat => type_name(table, config),
+    RustLayout::Modules => format!(
+      "super::{}::{}",
+      module_name(table),
+      type_name(table, config)
+    ),
+  }
+}
+
+// The newtype for a table's primary key
+fn id_name(table: &Table, config: &Config) -> String {
+  format!("{}Id", table.type_name(config))
+}
+
+// Another table's id type, which lives alongside that table's struct
+fn id_path(table: &Table, config: &Config) -> String {
+  match config.rust.layout {
+    RustLayout::Flat => id_name(table, config),
+    RustLayout::Modules => format!("super::{}::{}", module_name(table), id_name(table, config)),
+  }
+}
+
+// A newtype around the primary key's own type, so the id of one table can't
+// be passed where another's is wanted. It's serialised and stored as the key.
+fn id_declaration(table: &Table, key: &ColumnData, config: &Config) -> String {
+  let inner = value_type(key, &[], config);
+  let mut derives = config
+    .rust
+    .derives
+    .iter()
+    .map(String::as_str)
+    .collect::<Vec<&str>>();
+  let copy = inner != "String";
+  let extra = [
+    "Clone",
+    "Copy",
+    "PartialEq",
+    "Eq",
+    "Hash",
+    "PartialOrd",
+    "Ord",
+    "Default",
+  ]
+  .into_iter()
+  .filter(|derive| copy || *derive != "Copy");
+  for derive in extra {
+    if !derives
+      .iter()
+      .any(|known| known.rsplit("::").next() == Some(derive))
+    {
+      derives.push(derive);
+    }
+  }
+  let mut lines = vec![];
+  let serde = derives
+    .iter()
+    .any(|derive| derive.ends_with("Serialize") || derive.ends_with("Deserialize"));
+  if config.rust.flavour == RustFlavour::Sqlx {
+    derives.push("sqlx::Type");
+  }
+  lines.push(format!("#[derive({})]", derives.join(", ")));
+  if serde {
+    lines.push("#[serde(transparent)]".to_string());
+  }
+  if config.rust.flavour == RustFlavour::Sqlx {
+    lines.push("#[sqlx(transparent)]".to_string());
+  }
+  let visibility = visibility(config);
+  lines.push(format!(
+    "{}struct {}({}{});",
+    visibility,
+    id_name(table, config),
+    visibility,
+    inner
+  ));
+  lines.join("\n")
 }
 
+// A table on its own doesn't know what points at it, so it has no
+// associations, and its keys keep their own types
 impl ToRust for Table {
-  fn to_rust(&self) -> String {
-    let spec = self.columns.iter().fold(String::new(), |spec, column| {
-      [spec, "   ".to_owned() + &column.to_rust()].join("\n")
+  fn to_rust(&self, config: &Config) -> String {
+    table_to_rust(self, &[], &[], config)
+  }
+}
+
+// Associations are only there when they were loaded, so they can be left out
+fn association_field(association: &Association, config: &Config) -> String {
+  let name = type_path(association.table, config);
+  let (value_type, is_empty) = match association.kind {
+    AssociationKind::BelongsTo => (format!("Option<Box<{}>>", name), "Option::is_none"),
+    AssociationKind::HasMany => (format!("Vec<{}>", name), "Vec::is_empty"),
+  };
+  field_with(
+    &association.name,
+    &value_type,
+    &[
+      "default".to_string(),
+      format!("skip_serializing_if = {:?}", is_empty),
+    ],
+    config,
+  )
+}
+
+// What sqlx needs to know about a field before it can read it from a row
+fn sqlx_attribute(column_name: Option<&str>, config: &Config) -> String {
+  match (config.rust.flavour, column_name) {
+    (RustFlavour::Plain, _) => String::new(),
+    (RustFlavour::Sqlx, None) => "#[sqlx(skip)]\n   ".to_string(),
+    (RustFlavour::Sqlx, Some(column_name)) => {
+      let name = field_name(column_name, config);
+      if name.strip_prefix("r#").unwrap_or(&name) == column_name {
+        String::new()
+      } else {
+        format!("#[sqlx(rename = {:?})]\n   ", column_name)
+      }
+    }
+  }
+}
+
+fn table_to_rust(
+  table: &Table,
+  associations: &[Association],
+  keys: &[(&ColumnData, &Table)],
+  config: &Config,
+) -> String {
+  let spec = table.columns.iter().fold(String::new(), |spec, column| {
+    [
+      spec,
+      "   ".to_owned()
+        + &sqlx_attribute(Some(&column.name), config)
+        + &column_field(column, keys, config),
+    ]
+    .join("\n")
+  });
+  let spec = associations.iter().fold(spec, |spec, association| {
+    [
+      spec,
+      "   ".to_owned() + &sqlx_attribute(None, config) + &association_field(association, config),
+    ]
+    .join("\n")
+  });
+
+  let name = type_name(table, config);
+  let derives: &[&str] = match config.rust.flavour {
+    RustFlavour::Plain => &[],
+    RustFlavour::Sqlx => &["sqlx::FromRow"],
+  };
+  let mut items = vec![];
+  let own_key = keys
+    .iter()
+    .find(|(column, owner)| owner.name == table.name && column.options.foreign_key.is_none());
+  if let Some((key, _)) = own_key {
+    items.push(id_declaration(table, key, config));
+  }
+  items.push(declaration_with(&name, &spec, derives, &[], config));
+  if config.rust.defaults && table.has_literal_defaults() {
+    items.push(default_impl(table, associations, keys, &name, config));
+  }
+  if config.rust.variants.enabled {
+    items.push(variant(table, &name, Variant::Insert, keys, config));
+    items.push(variant(table, &name, Variant::Patch, keys, config));
+  }
+  if config.rust.flavour == RustFlavour::Sqlx && config.rust.crud {
+    items.push(crud_impl(table, &name, keys, config));
+  }
+  items.join("\n\n")
+}
+
+// Words Postgres, MySQL and SQLite won't take as a bare column or table name
+const SQL_KEYWORDS: &[&str] = &[
+  "all",
+  "and",
+  "any",
+  "as",
+  "asc",
+  "between",
+  "by",
+  "case",
+  "check",
+  "column",
+  "constraint",
+  "create",
+  "cross",
+  "default",
+  "delete",
+  "desc",
+  "distinct",
+  "drop",
+  "else",
+  "end",
+  "exists",
+  "false",
+  "for",
+  "foreign",
+  "from",
+  "full",
+  "group",
+  "having",
+  "in",
+  "index",
+  "inner",
+  "insert",
+  "into",
+  "is",
+  "join",
+  "key",
+  "left",
+  "like",
+  "limit",
+  "not",
+  "null",
+  "offset",
+  "on",
+  "or",
+  "order",
+  "primary",
+  "references",
+  "right",
+  "select",
+  "set",
+  "table",
+  "then",
+  "to",
+  "true",
+  "union",
+  "unique",
+  "update",
+  "user",
+  "using",
+  "values",
+  "when",
+  "where",
+  "with",
+];
+
+// A table or column name as it's written in a query, quoted if it has to be
+fn sql_identifier(name: &str, config: &Config) -> String {
+  let bare = name.starts_with(|first: char| first.is_ascii_lowercase() || first == '_')
+    && name.chars().all(|character| {
+      character.is_ascii_lowercase() || character.is_ascii_digit() || character == '_'
+    })
+    && !SQL_KEYWORDS.contains(&name);
+  match config.adapter {
+    _ if bare => name.to_string(),
+    Adapter::Mysql => format!("`{}`", name),
+    Adapter::Postgresql | Adapter::Sqlite => format!("\"{}\"", name),
+  }
+}
+
+// A query along with the values bound to it, one call to a line
+fn query(function: &str, sql: &str, binds: &[String], fetch: &str) -> String {
+  let binds = binds
+    .iter()
+    .map(|bind| format!("\n            .bind({})", bind))
+    .collect::<String>();
+  format!(
+    "        sqlx::{}({:?}){}\n            .{}(executor)\n            .await",
+    function, sql, binds, fetch
+  )
+}
+
+// Functions for reading and writing a row by its primary key. MySQL can't
+// return the row it wrote, so there insert and update return the new row's
+// id and the number of rows changed instead.
+fn crud_impl(table: &Table, name: &str, keys: &[(&ColumnData, &Table)], config: &Config) -> String {
+  let executor = match config.adapter {
+    Adapter::Postgresql => "impl sqlx::PgExecutor<'_>",
+    Adapter::Mysql => "impl sqlx::MySqlExecutor<'_>",
+    Adapter::Sqlite => "impl sqlx::SqliteExecutor<'_>",
+  };
+  let placeholder = |index: usize| match config.adapter {
+    Adapter::Postgresql => format!("${}", index),
+    Adapter::Mysql | Adapter::Sqlite => "?".to_string(),
+  };
+  let returning = config.adapter != Adapter::Mysql;
+  let table_name = sql_identifier(&table.name, config);
+  let column_list = |columns: &[&ColumnData]| {
+    columns
+      .iter()
+      .map(|column| sql_identifier(&column.name, config))
+      .collect::<Vec<String>>()
+      .join(", ")
+  };
+  let all_columns = table.columns.iter().collect::<Vec<&ColumnData>>();
+  let id_types = keys;
+  let keys = table
+    .columns
+    .iter()
+    .filter(|column| column.is_primary_key())
+    .collect::<Vec<&ColumnData>>();
+  let conditions = |first: usize| {
+    keys
+      .iter()
+      .enumerate()
+      .map(|(index, column)| {
+        format!(
+          "{} = {}",
+          sql_identifier(&column.name, config),
+          placeholder(first + index)
+        )
+      })
+      .collect::<Vec<String>>()
+      .join(" AND ")
+  };
+  let self_binds = |columns: &[&ColumnData]| {
+    columns
+      .iter()
+      .map(|column| format!("&self.{}", field_name(&column.name, config)))
+      .collect::<Vec<String>>()
+  };
+  let mut functions = vec![];
+
+  if !keys.is_empty() {
+    let parameters = keys
+      .iter()
+      .map(|column| {
+        format!(
+          ", {}: {}",
+          field_name(&column.name, config),
+          value_type(column, id_types, config)
+        )
+      })
+      .collect::<String>();
+    let sql = format!(
+      "SELECT {} FROM {} WHERE {}",
+      column_list(&all_columns),
+      table_name,
+      conditions(1)
+    );
+    let binds = keys
+      .iter()
+      .map(|column| field_name(&column.name, config))
+      .collect::<Vec<String>>();
+    functions.push(format!(
+      "    {}async fn find_by_id(executor: {}{}) -> sqlx::Result<Option<Self>> {{\n{}\n    }}",
+      visibility(config),
+      executor,
+      parameters,
+      query("query_as", &sql, &binds, "fetch_optional")
+    ));
+  }
+
+  let inserted = table
+    .variant_columns(Variant::Insert)
+    .into_iter()
+    .map(|(column, _)| column)
+    .collect::<Vec<&ColumnData>>();
+  let values = if inserted.is_empty() {
+    "DEFAULT VALUES".to_string()
+  } else {
+    format!(
+      "({}) VALUES ({})",
+      column_list(&inserted),
+      (1..=inserted.len())
+        .map(placeholder)
+        .collect::<Vec<String>>()
+        .join(", ")
+    )
+  };
+  let sql = format!("INSERT INTO {} {}", table_name, values);
+  functions.push(if returning {
+    format!(
+      "    {}async fn insert(&self, executor: {}) -> sqlx::Result<Self> {{\n{}\n    }}",
+      visibility(config),
+      executor,
+      query(
+        "query_as",
+        &format!("{} RETURNING {}", sql, column_list(&all_columns)),
+        &self_binds(&inserted),
+        "fetch_one"
+      )
+    )
+  } else {
+    format!(
+      "    {}async fn insert(&self, executor: {}) -> sqlx::Result<u64> {{\n{}\n            .map(|result| result.last_insert_id())\n    }}",
+      visibility(config),
+      executor,
+      query("query", &sql, &self_binds(&inserted), "execute")
+    )
+  });
+
+  let updated = table
+    .columns
+    .iter()
+    .filter(|column| !column.is_primary_key())
+    .collect::<Vec<&ColumnData>>();
+  if !keys.is_empty() && !updated.is_empty() {
+    let assignments = updated
+      .iter()
+      .enumerate()
+      .map(|(index, column)| {
+        format!(
+          "{} = {}",
+          sql_identifier(&column.name, config),
+          placeholder(index + 1)
+        )
+      })
+      .collect::<Vec<String>>()
+      .join(", ");
+    let sql = format!(
+      "UPDATE {} SET {} WHERE {}",
+      table_name,
+      assignments,
+      conditions(updated.len() + 1)
+    );
+    let binds = [self_binds(&updated), self_binds(&keys)].concat();
+    functions.push(if returning {
+      format!(
+        "    {}async fn update(&self, executor: {}) -> sqlx::Result<Self> {{\n{}\n    }}",
+        visibility(config),
+        executor,
+        query(
+          "query_as",
+          &format!("{} RETURNING {}", sql, column_list(&all_columns)),
+          &binds,
+          "fetch_one"
+        )
+      )
+    } else {
+      format!(
+        "    {}async fn update(&self, executor: {}) -> sqlx::Result<u64> {{\n{}\n            .map(|result| result.rows_affected())\n    }}",
+        visibility(config),
+        executor,
+        query("query", &sql, &binds, "execute")
+      )
     });
+  }
+
+  if !keys.is_empty() {
+    let sql = format!("DELETE FROM {} WHERE {}", table_name, conditions(1));
+    functions.push(format!(
+      "    {}async fn delete(&self, executor: {}) -> sqlx::Result<u64> {{\n{}\n            .map(|result| result.rows_affected())\n    }}",
+      visibility(config),
+      executor,
+      query("query", &sql, &self_binds(&keys), "execute")
+    ));
+  }
+
+  format!("impl {} {{\n{}\n}}", name, functions.join("\n\n"))
+}
 
-    let name = format_name(&self.name);
-    format!("struct {} {{{}\n}}", name, spec)
+// A patch needs to tell "set this column to null" apart from "leave it
+// alone", so nullable columns end up doubly wrapped
+fn variant_field(
+  column: &ColumnData,
+  optional: bool,
+  variant: Variant,
+  keys: &[(&ColumnData, &Table)],
+  config: &Config,
+) -> String {
+  if !optional {
+    return column_field(column, keys, config);
+  }
+  let value_type = value_type(column, keys, config);
+  let format = time_format(&value_type);
+  let mut serde = vec![
+    "default".to_string(),
+    "skip_serializing_if = \"Option::is_none\"".to_string(),
+  ];
+  let value_type = if column.nullable && variant == Variant::Patch {
+    serde.push(match format {
+      Some(format) => format!("with = \"{}\"", patch_format(format)),
+      None => "deserialize_with = \"double_option\"".to_string(),
+    });
+    format!("Option<Option<{}>>", value_type)
+  } else {
+    serde.extend(format.map(|format| format!("with = \"{}::option\"", format)));
+    format!("Option<{}>", value_type)
+  };
+  field_with(&column.name, &value_type, &serde, config)
+}
+
+fn variant(
+  table: &Table,
+  name: &str,
+  variant: Variant,
+  keys: &[(&ColumnData, &Table)],
+  config: &Config,
+) -> String {
+  let spec =
+    table
+      .variant_columns(variant)
+      .into_iter()
+      .fold(String::new(), |spec, (column, optional)| {
+        [
+          spec,
+          "   ".to_owned() + &variant_field(column, optional, variant, keys, config),
+        ]
+        .join("\n")
+      });
+  let name = config.rust.variants.name(variant, name);
+  declaration(&name, &spec, config)
+}
+
+// Renders a literal default as an expression of the column's rust type
+fn default_literal(value: &DefaultValue, value_type: &str) -> Option<String> {
+  match (value, value_type) {
+    (DefaultValue::Boolean(boolean), "bool") => Some(boolean.to_string()),
+    (DefaultValue::Integer(integer), "i16" | "i32" | "i64") => Some(integer.to_string()),
+    (value, "f64") => Some(format!("{:?}", value.as_number()?.parse::<f64>().ok()?)),
+    (DefaultValue::String(string), "String") => Some(format!("{:?}.to_string()", string)),
+    (
+      value,
+      "rust_decimal::Decimal"
+      | "bigdecimal::BigDecimal"
+      | "sqlx::types::Decimal"
+      | "sqlx::types::BigDecimal",
+    ) => Some(format!("{:?}.parse().unwrap()", value.as_number()?)),
+    (DefaultValue::String(string), value_type)
+      if value_type.starts_with("chrono::") || value_type.starts_with("sqlx::types::chrono::") =>
+    {
+      Some(format!("{:?}.parse().unwrap()", string))
+    }
+    (DefaultValue::Array(values), value_type) if value_type.starts_with("Vec<") => {
+      let element_type = &value_type[4..value_type.len() - 1];
+      let values = values
+        .iter()
+        .map(|value| default_literal(value, element_type))
+        .collect::<Option<Vec<String>>>()?;
+      Some(format!("vec![{}]", values.join(", ")))
+    }
+    (value, "serde_json::Value") => Some(format!("serde_json::json!({})", value.to_json()?)),
+    (value, "sqlx::types::Json<serde_json::Value>") => Some(format!(
+      "sqlx::types::Json(serde_json::json!({}))",
+      value.to_json()?
+    )),
+    (DefaultValue::Hash(pairs), "std::collections::HashMap<String,String>") if pairs.is_empty() => {
+      Some("std::collections::HashMap::new()".to_string())
+    }
+    _ => None,
+  }
+}
+
+// The value a field takes when it has no default of its own. The time
+// crate's types don't implement Default, so they start at the epoch instead,
+// and enums start at their first variant.
+fn zero_value(column: &ColumnData, keys: &[(&ColumnData, &Table)], config: &Config) -> String {
+  if column.nullable {
+    return "Default::default()".to_string();
+  }
+  if let Some((variant, _)) = sqlx_enum(column, config)
+    .filter(|_| !column.options.array)
+    .and_then(|enum_type| enum_variants(enum_type, config).into_iter().next())
+  {
+    return format!("{}::{}", value_type(column, keys, config), variant);
+  }
+  let zero_value = match value_type(column, keys, config).as_str() {
+    "time::OffsetDateTime" => "time::OffsetDateTime::UNIX_EPOCH",
+    "time::Date" => "time::OffsetDateTime::UNIX_EPOCH.date()",
+    "time::Time" => "time::Time::MIDNIGHT",
+    "sqlx::types::time::PrimitiveDateTime" => "sqlx::types::time::PrimitiveDateTime::MIN",
+    "sqlx::types::time::Date" => "sqlx::types::time::Date::MIN",
+    "sqlx::types::time::Time" => "sqlx::types::time::Time::MIDNIGHT",
+    _ => "Default::default()",
+  };
+  zero_value.to_string()
+}
+
+// A label default as the enum variant it belongs to
+fn enum_literal(column: &ColumnData, value: &DefaultValue, config: &Config) -> Option<String> {
+  let (Some(enum_type), DefaultValue::String(label), false) =
+    (sqlx_enum(column, config), value, column.options.array)
+  else {
+    return None;
+  };
+  let (variant, _) = enum_variants(enum_type, config)
+    .into_iter()
+    .find(|(_, value)| *value == label)?;
+  Some(format!("{}::{}", value_type(column, &[], config), variant))
+}
+
+fn default_field(column: &ColumnData, keys: &[(&ColumnData, &Table)], config: &Config) -> String {
+  let name = field_name(&column.name, config);
+  let literal = match &column.typed_default(config.adapter) {
+    Some(DefaultValue::Expression(expression)) => {
+      return format!(
+        "{}: {}, // generated by the database: {}",
+        name,
+        zero_value(column, keys, config),
+        expression
+      );
+    }
+    Some(value) => enum_literal(column, value, config)
+      .or_else(|| default_literal(value, &value_type(column, keys, config))),
+    None => None,
+  };
+  match (literal, column.nullable) {
+    (Some(literal), true) => format!("{}: Some({}),", name, literal),
+    (Some(literal), false) => format!("{}: {},", name, literal),
+    (None, _) => format!("{}: {},", name, zero_value(column, keys, config)),
+  }
+}
+
+fn default_impl(
+  table: &Table,
+  associations: &[Association],
+  keys: &[(&ColumnData, &Table)],
+  name: &str,
+  config: &Config,
+) -> String {
+  let fields = table.columns.iter().fold(String::new(), |fields, column| {
+    [
+      fields,
+      "            ".to_owned() + &default_field(column, keys, config),
+    ]
+    .join("\n")
+  });
+  let fields = associations.iter().fold(fields, |fields, association| {
+    [
+      fields,
+      format!(
+        "            {}: Default::default(),",
+        field_name(&association.name, config)
+      ),
+    ]
+    .join("\n")
+  });
+  format!(
+    "impl Default for {} {{\n    fn default() -> Self {{\n        Self {{{}\n        }}\n    }}\n}}",
+    name, fields
+  )
+}
+
+impl ToRust for NumericType {
+  fn to_rust(&self, config: &Config) -> String {
+    match (self, config.rust.decimal) {
+      (NumericType::SmallInt, _) => "i16".to_string(),
+      (NumericType::Int, _) => "i32".to_string(),
+      (NumericType::BigInt, _) => "i64".to_string(),
+      (NumericType::Float, _) => "f64".to_string(),
+      (NumericType::Decimal, RustDecimal::RustDecimal) => "rust_decimal::Decimal".to_string(),
+      (NumericType::Decimal, RustDecimal::BigDecimal) => "bigdecimal::BigDecimal".to_string(),
+      (NumericType::Decimal, RustDecimal::F64) => "f64".to_string(),
+      (NumericType::Decimal, RustDecimal::String) => "String".to_string(),
+    }
+  }
+}
+
+fn date_time_type(column: &RailsColumn, date_time: RustDateTime) -> &'static str {
+  match (date_time, column) {
+    (RustDateTime::Chrono, RailsColumn::Datetime) => "chrono::DateTime<chrono::Utc>",
+    (RustDateTime::Chrono, RailsColumn::Date) => "chrono::NaiveDate",
+    (RustDateTime::Chrono, RailsColumn::Time) => "chrono::NaiveTime",
+    (RustDateTime::Time, RailsColumn::Datetime) => "time::OffsetDateTime",
+    (RustDateTime::Time, RailsColumn::Date) => "time::Date",
+    (RustDateTime::Time, RailsColumn::Time) => "time::Time",
+    _ => "String",
   }
 }
 
 impl ToRust for RailsColumn {
-  fn to_rust(&self) -> String {
+  fn to_rust(&self, config: &Config) -> String {
+    if let Some(numeric_type) = self.numeric_type(config.adapter) {
+      return numeric_type.to_rust(config);
+    }
     match self {
-      RailsColumn::PrimaryKey => "usize".to_string(),
+      RailsColumn::PrimaryKey
+      | RailsColumn::Integer
+      | RailsColumn::Bigint
+      | RailsColumn::Float
+      | RailsColumn::Decimal
+      | RailsColumn::Numeric => unreachable!("numeric columns are handled above"),
       RailsColumn::String => "String".to_string(),
       RailsColumn::Text => "String".to_string(),
-      RailsColumn::Integer => "i64".to_string(),
-      RailsColumn::Bigint => "i128".to_string(),
-      RailsColumn::Float => "f64".to_string(),
-      RailsColumn::Decimal => "f64".to_string(),
-      RailsColumn::Numeric => "i64".to_string(),
-      RailsColumn::Datetime => "String".to_string(),
-      RailsColumn::Time => "String".to_string(),
-      RailsColumn::Date => "String".to_string(),
+      RailsColumn::Datetime | RailsColumn::Time | RailsColumn::Date => {
+        date_time_type(self, config.rust.date_time).to_string()
+      }
       RailsColumn::Binary => "Vec<u8>".to_string(),
       RailsColumn::Boolean => "bool".to_string(),
       RailsColumn::HStore => "std::collections::HashMap<String,String>".to_string(),
-      RailsColumn::JsonB => "std::collections::HashMap<String,String>".to_string(),
+      RailsColumn::JsonB => "serde_json::Value".to_string(),
+      RailsColumn::Uuid => "uuid::Uuid".to_string(),
+      RailsColumn::Enum => "String".to_string(),
     }
   }
 }
 
-impl ToRust for ColumnData {
-  fn to_rust(&self) -> String {
-    if self.nullable {
-      format!("{}: Option<{}>,", self.name, self.value_type.to_rust())
-    } else {
-      format!("{}: {},", self.name, self.value_type.to_rust())
+// The enum behind a column, when the sqlx flavour gives it a type of its own
+fn sqlx_enum<'a>(column: &'a ColumnData, config: &Config) -> Option<&'a EnumType> {
+  match (&column.options.enum_type, config.rust.flavour) {
+    (Some(enum_type), RustFlavour::Sqlx) if !enum_type.values.is_empty() => Some(enum_type),
+    _ => None,
+  }
+}
+
+// The types sqlx decodes each column into. Rails timestamps don't have a
+// time zone and decimals are numeric whatever their scale, so neither can
+// use the plain mapping.
+fn sqlx_type(column: &ColumnData, config: &Config) -> String {
+  let time = config.rust.date_time == RustDateTime::Time;
+  if let Some(enum_type) = sqlx_enum(column, config) {
+    return match config.rust.layout {
+      RustLayout::Flat => enum_name(&enum_type.name),
+      RustLayout::Modules => format!("super::{}", enum_name(&enum_type.name)),
+    };
+  }
+  match (&column.value_type, config.rust.decimal) {
+    (RailsColumn::Datetime, _) if time => "sqlx::types::time::PrimitiveDateTime".to_string(),
+    (RailsColumn::Date, _) if time => "sqlx::types::time::Date".to_string(),
+    (RailsColumn::Time, _) if time => "sqlx::types::time::Time".to_string(),
+    (RailsColumn::Datetime, _) => "sqlx::types::chrono::NaiveDateTime".to_string(),
+    (RailsColumn::Date, _) => "sqlx::types::chrono::NaiveDate".to_string(),
+    (RailsColumn::Time, _) => "sqlx::types::chrono::NaiveTime".to_string(),
+    // sqlx can't read SQLite's decimals as anything but a float
+    (RailsColumn::Decimal | RailsColumn::Numeric, _) if config.adapter == Adapter::Sqlite => {
+      "f64".to_string()
     }
+    (RailsColumn::Decimal | RailsColumn::Numeric, RustDecimal::RustDecimal) => {
+      "sqlx::types::Decimal".to_string()
+    }
+    (RailsColumn::Decimal | RailsColumn::Numeric, RustDecimal::BigDecimal) => {
+      "sqlx::types::BigDecimal".to_string()
+    }
+    (RailsColumn::Decimal | RailsColumn::Numeric, _) => NumericType::Decimal.to_rust(config),
+    (RailsColumn::Uuid, _) => "sqlx::types::Uuid".to_string(),
+    (RailsColumn::JsonB, _) => "sqlx::types::Json<serde_json::Value>".to_string(),
+    (RailsColumn::HStore, _) => "sqlx::postgres::types::PgHstore".to_string(),
+    (value_type, _) => match column.numeric_type(config.adapter) {
+      Some(numeric_type) => numeric_type.to_rust(config),
+      None => value_type.to_rust(config),
+    },
+  }
+}
+
+// The column's type, or the id type of the table whose key it holds
+fn value_type(column: &ColumnData, keys: &[(&ColumnData, &Table)], config: &Config) -> String {
+  let key = keys
+    .iter()
+    .find(|(key, _)| key.name == column.name)
+    .map(|(_, table)| table);
+  let value_type = match (
+    key,
+    column.numeric_type(config.adapter),
+    config.rust.flavour,
+  ) {
+    // A table's own key is declared right next to it
+    (Some(table), _, _) if column.options.foreign_key.is_none() => id_name(table, config),
+    (Some(table), _, _) => id_path(table, config),
+    (None, _, RustFlavour::Sqlx) => sqlx_type(column, config),
+    (None, Some(numeric_type), RustFlavour::Plain) => numeric_type.to_rust(config),
+    (None, None, RustFlavour::Plain) => column.value_type.to_rust(config),
+  };
+  if column.options.array {
+    format!("Vec<{}>", value_type)
+  } else {
+    value_type
+  }
+}
+
+fn column_field(column: &ColumnData, keys: &[(&ColumnData, &Table)], config: &Config) -> String {
+  let value_type = value_type(column, keys, config);
+  match (time_format(&value_type), column.nullable) {
+    (Some(format), true) => field_with(
+      &column.name,
+      &format!("Option<{}>", value_type),
+      &[
+        "default".to_string(),
+        format!("with = \"{}::option\"", format),
+      ],
+      config,
+    ),
+    (Some(format), false) => field_with(
+      &column.name,
+      &value_type,
+      &[format!("with = \"{}\"", format)],
+      config,
+    ),
+    (None, true) => field(&column.name, &format!("Option<{}>", value_type), config),
+    (None, false) => field(&column.name, &value_type, config),
+  }
+}
+
+impl ToRust for ColumnData {
+  fn to_rust(&self, config: &Config) -> String {
+    column_field(self, &[], config)
   }
 }
 
 #[cfg(test)]
 pub mod test {
-  use crate::{rust::ToRust, Database};
+  use crate::{
+    case::Case,
+    config::Adapter,
+    config::Config,
+    rust::{RustDateTime, RustDecimal, RustFlavour, RustLayout, ToRust},
+    Database,
+  };
   #[test]
   fn can_convert_a_rails_schema_to_a_string_version_of_a_rust_struct() {
     let schema = "ActiveRecord::Schema.define(version: 20_210_916_202_951) do
@@ -97,25 +1392,747 @@ pub mod test {
   end
 end";
     assert_eq!(
-      Database::from(schema).to_rust(),
-      "struct SampleSchema {
-   a: Option<usize>,
-   b: Option<String>,
-   c: Option<String>,
-   d: Option<i64>,
-   e: Option<i128>,
-   f: Option<f64>,
-   g: Option<f64>,
-   h: Option<i64>,
-   i: Option<String>,
-   j: Option<String>,
-   k: Option<String>,
-   l: Option<Vec<u8>>,
-   m: Option<bool>,
-   n: Option<std::collections::HashMap<String,String>>,
-   o: Option<std::collections::HashMap<String,String>>,
-   created_at: String,
-   updated_at: String,
+      Database::from(schema).to_rust(&Config::default()),
+      "use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SampleSchema {
+   pub a: Option<i64>,
+   pub b: Option<String>,
+   pub c: Option<String>,
+   pub d: Option<i32>,
+   pub e: Option<i64>,
+   pub f: Option<f64>,
+   pub g: Option<Decimal>,
+   pub h: Option<Decimal>,
+   pub i: Option<String>,
+   pub j: Option<String>,
+   pub k: Option<String>,
+   pub l: Option<Vec<u8>>,
+   pub m: Option<bool>,
+   pub n: Option<HashMap<String,String>>,
+   pub o: Option<Value>,
+   pub created_at: String,
+   pub updated_at: String,
+}"
+    )
+  }
+
+  #[test]
+  fn integers_and_decimals_follow_their_limit_precision_and_scale() {
+    let schema = "create_table \"accounts\", id: false, force: :cascade do |t|
+    t.integer \"logins\", limit: 2, null: false
+    t.integer \"visits\", limit: 8, null: false
+    t.decimal \"balance\", precision: 10, scale: 2, null: false
+    t.decimal \"cents\", precision: 9, scale: 0, null: false
+  end";
+    let mut config = Config::default();
+    config.rust.decimal = RustDecimal::BigDecimal;
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+   pub logins: i16,
+   pub visits: i64,
+   pub balance: BigDecimal,
+   pub cents: i32,
+}"
+    )
+  }
+
+  #[test]
+  fn dates_and_times_can_use_chrono() {
+    let schema = "create_table \"events\", id: false, force: :cascade do |t|
+    t.datetime \"starts_at\", null: false
+    t.date \"day\", null: false
+    t.time \"doors_open\"
+  end";
+    let mut config = Config::default();
+    config.rust.date_time = RustDateTime::Chrono;
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+   pub starts_at: DateTime<Utc>,
+   pub day: NaiveDate,
+   pub doors_open: Option<NaiveTime>,
+}"
+    )
+  }
+
+  #[test]
+  fn column_defaults_become_an_impl_default() {
+    let schema = "create_table \"posts\", id: false, force: :cascade do |t|
+    t.string \"title\", null: false
+    t.string \"status\", default: \"draft\", null: false
+    t.integer \"views\", default: 0
+    t.decimal \"price\", precision: 10, scale: 2, default: \"9.99\", null: false
+    t.datetime \"published_at\", default: -> { \"now()\" }, null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_rust(&Config::default()),
+      "use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Post {
+   pub title: String,
+   pub status: String,
+   pub views: Option<i32>,
+   pub price: Decimal,
+   pub published_at: String,
+}
+
+impl Default for Post {
+    fn default() -> Self {
+        Self {
+            title: Default::default(),
+            status: \"draft\".to_string(),
+            views: Some(0),
+            price: \"9.99\".parse().unwrap(),
+            published_at: Default::default(), // generated by the database: now()
+        }
+    }
+}"
+    )
+  }
+
+  #[test]
+  fn tables_can_have_insert_and_patch_variants() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+    t.string \"name\", null: false
+    t.string \"nickname\"
+    t.datetime \"created_at\", null: false
+  end";
+    let mut config = Config::default();
+    config.rust.variants.enabled = true;
+    config.rust.variants.patch = "Update{}".to_string();
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use serde::{Deserialize, Serialize};
+
+// Reads a field that is there as `Some`, so a `null` is `Some(None)`
+fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: ::serde::Deserialize<'de>,
+    D: ::serde::Deserializer<'de>,
+{
+    ::serde::Deserialize::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+   pub id: i64,
+   pub name: String,
+   pub nickname: Option<String>,
+   pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewUser {
+   pub name: String,
+   #[serde(default, skip_serializing_if = \"Option::is_none\")]
+   pub nickname: Option<String>,
+   #[serde(default, skip_serializing_if = \"Option::is_none\")]
+   pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateUser {
+   #[serde(default, skip_serializing_if = \"Option::is_none\")]
+   pub name: Option<String>,
+   #[serde(default, skip_serializing_if = \"Option::is_none\", deserialize_with = \"double_option\")]
+   pub nickname: Option<Option<String>>,
+   #[serde(default, skip_serializing_if = \"Option::is_none\")]
+   pub created_at: Option<String>,
+}"
+    )
+  }
+
+  #[test]
+  fn foreign_keys_can_become_association_fields() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+  end
+  create_table \"posts\", force: :cascade do |t|
+    t.bigint \"author_id\", null: false
+  end
+  add_foreign_key \"posts\", \"users\", column: \"author_id\"";
+    let mut config = Config::default();
+    config.rust.associations = true;
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+   pub id: i64,
+   #[serde(default, skip_serializing_if = \"Vec::is_empty\")]
+   pub posts: Vec<Post>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Post {
+   pub id: i64,
+   pub author_id: i64,
+   #[serde(default, skip_serializing_if = \"Option::is_none\")]
+   pub author: Option<Box<User>>,
+}"
+    )
+  }
+
+  #[test]
+  fn keys_can_have_an_id_type_of_their_own() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+  end
+  create_table \"posts\", id: :uuid, force: :cascade do |t|
+    t.bigint \"author_id\", null: false
+    t.bigint \"editor_id\"
+  end
+  add_foreign_key \"posts\", \"users\", column: \"author_id\"
+  add_foreign_key \"posts\", \"users\", column: \"editor_id\"";
+    let mut config = Config::default();
+    config.rust.id_types = true;
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Copy, Eq, Hash, PartialOrd, Ord, Default)]
+#[serde(transparent)]
+pub struct UserId(pub i64);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+   pub id: UserId,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Copy, Eq, Hash, PartialOrd, Ord, Default)]
+#[serde(transparent)]
+pub struct PostId(pub Uuid);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Post {
+   pub id: PostId,
+   pub author_id: UserId,
+   pub editor_id: Option<UserId>,
+}"
+    )
+  }
+
+  #[test]
+  fn awkward_table_names_do_not_panic() {
+    let schema = "create_table \"_legacy__audit_rows\", id: false, force: :cascade do |t|
+    t.string \"source_url\"
+  end";
+    let config = Config {
+      acronyms: vec!["URL".to_string()],
+      ..Config::default()
+    };
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegacyAuditRow {
+   pub source_url: Option<String>,
+}"
+    )
+  }
+
+  #[test]
+  fn reserved_words_and_invalid_identifiers_are_escaped() {
+    let schema = "create_table \"2fa_codes\", id: false, force: :cascade do |t|
+    t.string \"type\", null: false
+    t.string \"self\", null: false
+    t.string \"_\", null: false
+    t.string \"__\", null: false
+    t.integer \"range\", null: false
+    t.string \"123abc\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_rust(&Config::default()),
+      "use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct _2faCode {
+   pub r#type: String,
+   #[serde(rename = \"self\")]
+   pub self_: String,
+   #[serde(rename = \"_\")]
+   pub __: String,
+   #[serde(rename = \"__\")]
+   pub ___: String,
+   pub range: i32,
+   #[serde(rename = \"123abc\")]
+   pub _123abc: String,
+}"
+    )
+  }
+
+  #[test]
+  fn names_are_escaped_after_they_are_put_in_their_case() {
+    let schema = "create_table \"things\", id: false, force: :cascade do |t|
+    t.string \"self\", null: false
+    t.string \"___\", null: false
+  end";
+    let mut config = Config::default();
+    config.rust.field_case = Case::Pascal;
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Thing {
+   #[serde(rename = \"self\")]
+   pub Self_: String,
+   #[serde(rename = \"___\")]
+   pub ____: String,
+}"
+    )
+  }
+
+  #[test]
+  fn renamed_fields_serialize_under_their_column_name() {
+    let schema = "create_table \"users\", id: false, force: :cascade do |t|
+    t.string \"first_name\", null: false
+    t.string \"legacyCode\", null: false
+  end";
+    let mut config = Config::default();
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+   pub first_name: String,
+   #[serde(rename = \"legacyCode\")]
+   pub legacy_code: String,
+}"
+    );
+
+    config.rust.rename_all = Some(Case::Camel);
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = \"camelCase\")]
+pub struct User {
+   pub first_name: String,
+   pub legacy_code: String,
+}"
+    );
+
+    config.rust.rename_all = None;
+    config.rust.field_case = Case::Camel;
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+   #[serde(rename = \"first_name\")]
+   pub firstName: String,
+   pub legacyCode: String,
+}"
+    )
+  }
+
+  #[test]
+  fn structs_can_be_private_with_their_own_derives_and_attributes() {
+    let schema = "create_table \"users\", id: false, force: :cascade do |t|
+    t.uuid \"id\", null: false
+  end";
+    let mut config = Config::default();
+    config.rust.visibility = String::new();
+    config.rust.derives = vec!["Debug".to_string()];
+    config.rust.attributes = vec!["#[non_exhaustive]".to_string()];
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use uuid::Uuid;
+
+#[derive(Debug)]
+#[non_exhaustive]
+struct User {
+   id: Uuid,
+}"
+    )
+  }
+
+  #[test]
+  fn each_table_can_have_a_module_of_its_own() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+    t.datetime \"created_at\", null: false
+  end
+  create_table \"posts\", force: :cascade do |t|
+    t.bigint \"user_id\", null: false
+  end
+  add_foreign_key \"posts\", \"users\"";
+    let mut config = Config::default();
+    config.rust.layout = RustLayout::Modules;
+    config.rust.associations = true;
+    config.rust.date_time = RustDateTime::Chrono;
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "pub mod users {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct User {
+       pub id: i64,
+       pub created_at: DateTime<Utc>,
+       #[serde(default, skip_serializing_if = \"Vec::is_empty\")]
+       pub posts: Vec<super::posts::Post>,
+    }
+}
+
+pub mod posts {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    pub struct Post {
+       pub id: i64,
+       pub user_id: i64,
+       #[serde(default, skip_serializing_if = \"Option::is_none\")]
+       pub user: Option<Box<super::users::User>>,
+    }
+}"
+    )
+  }
+
+  #[test]
+  fn the_output_is_valid_rust() {
+    let schema = "create_table \"sample_schema\", force: :cascade do |t|
+    t.string \"type\", default: \"draft\", null: false
+    t.text \"123abc\"
+    t.integer \"count\", limit: 2, default: 0
+    t.decimal \"price\", precision: 10, scale: 2, default: \"9.99\"
+    t.datetime \"created_at\", default: -> { \"now()\" }, null: false
+    t.date \"day\"
+    t.binary \"data\"
+    t.boolean \"active\", default: true
+    t.hstore \"tags\", default: {}
+    t.jsonb \"settings\", default: { \"theme\" => \"dark\" }
+    t.uuid \"token\"
+    t.bigint \"user_id\"
+    t.string \"labels\", array: true, default: [\"new\"], null: false
+    t.enum \"status\", enum_type: \"status\", default: \"in-review\", null: false
+  end
+  create_table \"users\", force: :cascade do |t|
+  end
+  create_enum \"status\", [\"draft\", \"in-review\"]
+  add_foreign_key \"sample_schema\", \"users\"";
+    let mut config = Config::default();
+    config.rust.associations = true;
+    config.rust.variants.enabled = true;
+    config.rust.crud = true;
+    config.rust.id_types = true;
+    for date_time in [
+      RustDateTime::String,
+      RustDateTime::Chrono,
+      RustDateTime::Time,
+    ] {
+      for layout in [RustLayout::Flat, RustLayout::Modules] {
+        for flavour in [RustFlavour::Plain, RustFlavour::Sqlx] {
+          config.rust.date_time = date_time;
+          config.rust.layout = layout;
+          config.rust.flavour = flavour;
+          let rust = Database::from(schema).to_rust(&config);
+          if let Err(error) = syn::parse_file(&rust) {
+            panic!("{}\n\n{}", error, rust);
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn array_columns_become_vecs() {
+    let schema = "create_table \"posts\", id: false, force: :cascade do |t|
+    t.string \"tags\", array: true, default: [\"new\"], null: false
+    t.integer \"scores\", array: true
+  end";
+    assert_eq!(
+      Database::from(schema).to_rust(&Config::default()),
+      "use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Post {
+   pub tags: Vec<String>,
+   pub scores: Option<Vec<i32>>,
+}
+
+impl Default for Post {
+    fn default() -> Self {
+        Self {
+            tags: vec![\"new\".to_string()],
+            scores: Default::default(),
+        }
+    }
+}"
+    );
+  }
+
+  #[test]
+  fn the_sqlx_flavour_reads_rows_with_sqlx_types() {
+    let schema = "create_enum \"mood\", [\"happy\", \"not-sure\"]
+  create_table \"people\", id: :uuid, force: :cascade do |t|
+    t.enum \"mood\", enum_type: \"mood\", default: \"not-sure\", null: false
+    t.string \"type\"
+    t.jsonb \"settings\"
+    t.decimal \"balance\", precision: 10, scale: 2
+    t.datetime \"created_at\", null: false
+  end";
+    let mut config = Config::default();
+    config.rust.flavour = RustFlavour::Sqlx;
+    config.rust.decimal = RustDecimal::BigDecimal;
+    config.rust.date_time = RustDateTime::Chrono;
+    config.rust.field_case = Case::Camel;
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::types::{BigDecimal, Json, Uuid};
+use sqlx::types::chrono::NaiveDateTime;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = \"mood\", rename_all = \"snake_case\")]
+#[serde(rename_all = \"snake_case\")]
+pub enum Mood {
+    Happy,
+    #[serde(rename = \"not-sure\")]
+    #[sqlx(rename = \"not-sure\")]
+    NotSure,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Person {
+   pub id: Uuid,
+   pub mood: Mood,
+   pub r#type: Option<String>,
+   pub settings: Option<Json<Value>>,
+   pub balance: Option<BigDecimal>,
+   #[sqlx(rename = \"created_at\")]
+   #[serde(rename = \"created_at\")]
+   pub createdAt: NaiveDateTime,
+}
+
+impl Default for Person {
+    fn default() -> Self {
+        Self {
+            id: Default::default(),
+            mood: Mood::NotSure,
+            r#type: Default::default(),
+            settings: Default::default(),
+            balance: Default::default(),
+            createdAt: Default::default(),
+        }
+    }
+}"
+    );
+  }
+
+  #[test]
+  fn the_sqlx_flavour_can_add_crud_functions() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+    t.string \"name\", null: false
+    t.string \"order\"
+  end";
+    let mut config = Config::default();
+    config.rust.flavour = RustFlavour::Sqlx;
+    config.rust.crud = true;
+    config.rust.derives = vec!["sqlx::FromRow".to_string()];
+    assert_eq!(Database::from(schema).to_rust(&config),
+      "#[derive(sqlx::FromRow, sqlx::FromRow)]
+pub struct User {
+   pub id: i64,
+   pub name: String,
+   pub order: Option<String>,
+}
+
+impl User {
+    pub async fn find_by_id(executor: impl sqlx::PgExecutor<'_>, id: i64) -> sqlx::Result<Option<Self>> {
+        sqlx::query_as(\"SELECT id, name, \\\"order\\\" FROM users WHERE id = $1\")
+            .bind(id)
+            .fetch_optional(executor)
+            .await
+    }
+
+    pub async fn insert(&self, executor: impl sqlx::PgExecutor<'_>) -> sqlx::Result<Self> {
+        sqlx::query_as(\"INSERT INTO users (name, \\\"order\\\") VALUES ($1, $2) RETURNING id, name, \\\"order\\\"\")
+            .bind(&self.name)
+            .bind(&self.order)
+            .fetch_one(executor)
+            .await
+    }
+
+    pub async fn update(&self, executor: impl sqlx::PgExecutor<'_>) -> sqlx::Result<Self> {
+        sqlx::query_as(\"UPDATE users SET name = $1, \\\"order\\\" = $2 WHERE id = $3 RETURNING id, name, \\\"order\\\"\")
+            .bind(&self.name)
+            .bind(&self.order)
+            .bind(&self.id)
+            .fetch_one(executor)
+            .await
+    }
+
+    pub async fn delete(&self, executor: impl sqlx::PgExecutor<'_>) -> sqlx::Result<u64> {
+        sqlx::query(\"DELETE FROM users WHERE id = $1\")
+            .bind(&self.id)
+            .execute(executor)
+            .await
+            .map(|result| result.rows_affected())
+    }
+}"
+    );
+
+    let config = Config {
+      adapter: Adapter::Mysql,
+      ..config
+    };
+    let rust = Database::from(schema).to_rust(&config);
+    assert!(rust.contains(
+      "sqlx::query(\"INSERT INTO users (name, `order`) VALUES (?, ?)\")
+            .bind(&self.name)
+            .bind(&self.order)
+            .execute(executor)
+            .await
+            .map(|result| result.last_insert_id())"
+    ));
+  }
+
+  #[test]
+  fn numeric_defaults_take_the_column_type() {
+    let schema = "create_table \"accounts\", id: false, force: :cascade do |t|
+    t.decimal \"balance\", precision: 10, scale: 2, default: \"0.0\", null: false
+    t.float \"ratio\", default: 0, null: false
+    t.decimal \"amount\", precision: 9, scale: 0, default: \"5\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_rust(&Config::default()),
+      "use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Account {
+   pub balance: Decimal,
+   pub ratio: f64,
+   pub amount: i32,
+}
+
+impl Default for Account {
+    fn default() -> Self {
+        Self {
+            balance: \"0.0\".parse().unwrap(),
+            ratio: 0.0,
+            amount: 5,
+        }
+    }
+}"
+    )
+  }
+
+  #[test]
+  fn time_columns_are_read_in_the_format_rails_writes() {
+    let schema = "create_table \"events\", id: false, force: :cascade do |t|
+    t.datetime \"starts_at\", null: false
+    t.datetime \"ends_at\"
+    t.date \"on\", null: false
+    t.time \"doors\"
+  end";
+    let mut config = Config::default();
+    config.rust.date_time = RustDateTime::Time;
+    config.rust.defaults = false;
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use serde::{Deserialize, Serialize};
+use time::{Date, OffsetDateTime, Time};
+
+::time::serde::format_description!(date_format, Date, \"[year]-[month]-[day]\");
+
+::time::serde::format_description!(
+    time_format,
+    Time,
+    \"[hour]:[minute]:[second][optional [.[subsecond]]]\"
+);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+   #[serde(with = \"::time::serde::rfc3339\")]
+   pub starts_at: OffsetDateTime,
+   #[serde(default, with = \"::time::serde::rfc3339::option\")]
+   pub ends_at: Option<OffsetDateTime>,
+   #[serde(with = \"date_format\")]
+   pub on: Date,
+   #[serde(default, with = \"time_format::option\")]
+   pub doors: Option<Time>,
+}"
+    )
+  }
+
+  #[test]
+  fn patches_keep_null_apart_from_a_missing_time() {
+    let schema = "create_table \"events\", id: false, force: :cascade do |t|
+    t.date \"on\", null: false
+    t.datetime \"ends_at\"
+  end";
+    let mut config = Config::default();
+    config.rust.date_time = RustDateTime::Time;
+    config.rust.variants.enabled = true;
+    assert_eq!(
+      Database::from(schema).to_rust(&config),
+      "use serde::{Deserialize, Serialize};
+use time::{Date, OffsetDateTime};
+
+::time::serde::format_description!(date_format, Date, \"[year]-[month]-[day]\");
+
+mod rfc3339_patch {
+    pub fn serialize<S: ::serde::Serializer>(
+        value: &Option<Option<::time::OffsetDateTime>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ::time::serde::rfc3339::option::serialize(&value.flatten(), serializer)
+    }
+
+    pub fn deserialize<'de, D: ::serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Option<::time::OffsetDateTime>>, D::Error> {
+        ::time::serde::rfc3339::option::deserialize(deserializer).map(Some)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+   #[serde(with = \"date_format\")]
+   pub on: Date,
+   #[serde(default, with = \"::time::serde::rfc3339::option\")]
+   pub ends_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewEvent {
+   #[serde(with = \"date_format\")]
+   pub on: Date,
+   #[serde(default, skip_serializing_if = \"Option::is_none\", with = \"::time::serde::rfc3339::option\")]
+   pub ends_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventPatch {
+   #[serde(default, skip_serializing_if = \"Option::is_none\", with = \"date_format::option\")]
+   pub on: Option<Date>,
+   #[serde(default, skip_serializing_if = \"Option::is_none\", with = \"rfc3339_patch\")]
+   pub ends_at: Option<Option<OffsetDateTime>>,
 }"
     )
   }
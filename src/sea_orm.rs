@@ -0,0 +1,747 @@
+use super::case::Case;
+use super::config::{Adapter, Config};
+use super::rust::{
+  declaration_with, enum_declaration, enum_name, escape, field_name, field_named, module,
+  module_name, type_name, visibility, with_imports, RustDateTime, RustDecimal, ToRust,
+};
+use super::ColumnData;
+use super::Database;
+use super::RailsColumn;
+use super::Table;
+
+// The types every entity module declares, which an import mustn't shadow
+const ENTITY_TYPES: &[&str] = &[
+  "ActiveModel",
+  "Column",
+  "Entity",
+  "Model",
+  "PrimaryKey",
+  "Relation",
+];
+
+pub trait ToSeaOrm {
+  fn to_sea_orm(&self, config: &Config) -> String;
+}
+
+impl ToSeaOrm for Database {
+  fn to_sea_orm(&self, config: &Config) -> String {
+    // SeaORM can't describe a table without a primary key
+    let (tables, skipped): (Vec<&Table>, Vec<&Table>) = self
+      .0
+      .iter()
+      .partition(|table| table.columns.iter().any(ColumnData::is_primary_key));
+    let mut items = skipped
+      .iter()
+      .map(|table| {
+        format!(
+          "// {} has no primary key, so it can't be a SeaORM entity",
+          table.name
+        )
+      })
+      .collect::<Vec<String>>();
+
+    let enums = self
+      .enums()
+      .into_iter()
+      .filter(|enum_type| !enum_type.values.is_empty())
+      .map(|enum_type| {
+        enum_declaration(
+          enum_type,
+          &["EnumIter", "DeriveActiveEnum"],
+          &[format!(
+            "#[sea_orm(rs_type = \"String\", db_type = \"Enum\", enum_name = {:?}, rename_all = \"snake_case\")]",
+            enum_type.name
+          )],
+          &[("serde", "rename"), ("sea_orm", "string_value")],
+          config,
+        )
+      })
+      .collect::<Vec<String>>();
+    if !enums.is_empty() {
+      let names = self
+        .enums()
+        .iter()
+        .map(|enum_type| enum_name(&enum_type.name))
+        .collect::<Vec<String>>();
+      items.push(module(
+        "sea_orm_active_enums",
+        &prelude(&with_imports(&enums.join("\n\n"), &names)),
+        config,
+      ));
+    }
+
+    items.extend(tables.iter().map(|table| {
+      let names = ENTITY_TYPES
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<String>>();
+      module(
+        &module_name(table),
+        &prelude(&with_imports(&entity(table, &tables, config), &names)),
+        config,
+      )
+    }));
+
+    if !tables.is_empty() {
+      let entities = tables
+        .iter()
+        .map(|table| {
+          format!(
+            "{}use super::{}::Entity as {};",
+            visibility(config),
+            module_name(table),
+            type_name(table, config)
+          )
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+      items.push(module("prelude", &entities, config));
+    }
+    items.join("\n\n")
+  }
+}
+
+// Puts SeaORM's prelude ahead of any other imports
+fn prelude(body: &str) -> String {
+  let separator = if body.starts_with("use ") {
+    "\n"
+  } else {
+    "\n\n"
+  };
+  format!("use sea_orm::entity::prelude::*;{}{}", separator, body)
+}
+
+// The type SeaORM reads each column into, written with the names its
+// prelude exports. Rails timestamps don't have a time zone, and numeric
+// columns are read through sqlx, which only has a float for them on SQLite.
+fn value_type(column: &ColumnData, config: &Config) -> String {
+  let chrono = config.rust.date_time != RustDateTime::Time;
+  let value_type = match &column.value_type {
+    RailsColumn::Datetime if chrono => "DateTime".to_string(),
+    RailsColumn::Date if chrono => "Date".to_string(),
+    RailsColumn::Time if chrono => "Time".to_string(),
+    RailsColumn::Datetime => "TimeDateTime".to_string(),
+    RailsColumn::Date => "TimeDate".to_string(),
+    RailsColumn::Time => "TimeTime".to_string(),
+    RailsColumn::Decimal | RailsColumn::Numeric => match (config.adapter, config.rust.decimal) {
+      (Adapter::Sqlite, _) => "f64".to_string(),
+      (_, RustDecimal::BigDecimal) => "BigDecimal".to_string(),
+      _ => "Decimal".to_string(),
+    },
+    RailsColumn::Uuid => "Uuid".to_string(),
+    RailsColumn::JsonB => "Json".to_string(),
+    RailsColumn::HStore => "String".to_string(),
+    RailsColumn::Enum => match &column.options.enum_type {
+      Some(enum_type) if !enum_type.values.is_empty() => {
+        format!(
+          "super::sea_orm_active_enums::{}",
+          enum_name(&enum_type.name)
+        )
+      }
+      _ => "String".to_string(),
+    },
+    value_type => match column.numeric_type(config.adapter) {
+      Some(numeric_type) => numeric_type.to_rust(config),
+      None => value_type.to_rust(config),
+    },
+  };
+  if column.options.array {
+    format!("Vec<{}>", value_type)
+  } else {
+    value_type
+  }
+}
+
+// The column type, for the columns SeaORM can't work out from the field's type
+fn column_type(column: &ColumnData, config: &Config) -> Option<String> {
+  if column.options.array {
+    return None;
+  }
+  let options = &column.options;
+  match &column.value_type {
+    RailsColumn::Text => Some("Text".to_string()),
+    RailsColumn::String => options
+      .limit
+      .map(|limit| format!("String(StringLen::N({}))", limit)),
+    RailsColumn::Decimal | RailsColumn::Numeric => options.precision.map(|precision| {
+      format!(
+        "Decimal(Some(({}, {})))",
+        precision,
+        options.scale.unwrap_or(0)
+      )
+    }),
+    RailsColumn::JsonB if config.adapter == Adapter::Postgresql => Some("JsonBinary".to_string()),
+    RailsColumn::HStore => Some("custom(\"hstore\")".to_string()),
+    _ => None,
+  }
+}
+
+// A model field, along with what SeaORM needs to know about its column
+fn model_field(column: &ColumnData, composite_key: bool, config: &Config) -> String {
+  let mut options = vec![];
+  if column.is_primary_key() {
+    options.push("primary_key".to_string());
+    let auto_increment = matches!(
+      column.value_type,
+      RailsColumn::PrimaryKey | RailsColumn::Integer | RailsColumn::Bigint
+    );
+    if composite_key || !auto_increment {
+      options.push("auto_increment = false".to_string());
+    }
+  }
+  let name = model_field_name(column, config);
+  if name.strip_prefix("r#").unwrap_or(&name) != column.name {
+    options.push(format!("column_name = {:?}", column.name));
+  }
+  if let Some(column_type) = column_type(column, config) {
+    options.push(format!("column_type = {:?}", column_type));
+  }
+
+  let value_type = value_type(column, config);
+  let field = if column.nullable {
+    field_named(
+      &name,
+      &column.name,
+      &format!("Option<{}>", value_type),
+      &[],
+      config,
+    )
+  } else {
+    field_named(&name, &column.name, &value_type, &[], config)
+  };
+  if options.is_empty() {
+    format!("   {}", field)
+  } else {
+    format!("   #[sea_orm({})]\n   {}", options.join(", "), field)
+  }
+}
+
+// The field a column is read into, named the way the Rust format names it.
+// SeaORM names the column's variant after the field and panics when that
+// isn't an identifier, even with an `enum_name`, so a field like `_123abc`
+// is written `x_123abc` instead.
+fn model_field_name(column: &ColumnData, config: &Config) -> String {
+  let name = field_name(&column.name, config);
+  let identifier = name.strip_prefix("r#").unwrap_or(&name);
+  let variant = Case::Pascal.convert(identifier, &[]);
+  if variant.is_empty() || variant.starts_with(|first: char| first.is_ascii_digit()) {
+    format!("x{}", identifier)
+  } else {
+    name
+  }
+}
+
+// The variant SeaORM names a column by, escaped the way it escapes keywords,
+// which is the way the Rust format does
+fn column_variant(column: &ColumnData, config: &Config) -> String {
+  let name = model_field_name(column, config);
+  escape(Case::Pascal.convert(name.strip_prefix("r#").unwrap_or(&name), &[]))
+}
+
+// The foreign keys on `table` that point at a primary key of another entity,
+// paired with the entity they point at
+fn foreign_keys<'a>(
+  table: &'a Table,
+  tables: &[&'a Table],
+) -> Vec<(&'a ColumnData, &'a Table, &'a ColumnData)> {
+  table
+    .columns
+    .iter()
+    .filter_map(|column| {
+      let foreign_key = column.options.foreign_key.as_ref()?;
+      let parent = tables
+        .iter()
+        .find(|parent| parent.name == foreign_key.table)?;
+      let key = parent
+        .columns
+        .iter()
+        .find(|key| key.is_primary_key() && key.name == foreign_key.primary_key)?;
+      Some((column, *parent, key))
+    })
+    .collect()
+}
+
+// A variant of an entity's Relation enum and the entity it leads to
+struct Relation {
+  variant: String,
+  target: String,
+  // What DeriveRelation needs to define it, if it can
+  attribute: Option<String>,
+  // The same definition written out by hand
+  def: String,
+}
+
+// A belongs_to for each foreign key, named after the column, and a has_many
+// for each foreign key pointing back at this table. SeaORM derives a
+// has_many by following the other table's Related impl, so when that table
+// points here more than once each has_many is named after its foreign key
+// and follows the matching belongs_to back instead.
+fn relations(table: &Table, tables: &[&Table], config: &Config) -> Vec<Relation> {
+  let mut relations = vec![];
+  for (column, parent, key) in foreign_keys(table, tables) {
+    let name = column.name.strip_suffix("_id").unwrap_or(&column.name);
+    relations.push(Relation {
+      variant: enum_name(name),
+      target: module_name(parent),
+      attribute: Some(format!(
+        "#[sea_orm(belongs_to = \"super::{}::Entity\", from = \"Column::{}\", to = \"super::{}::Column::{}\")]",
+        module_name(parent),
+        column_variant(column, config),
+        module_name(parent),
+        column_variant(key, config)
+      )),
+      def: format!(
+        "Entity::belongs_to(super::{}::Entity)\n    .from(Column::{})\n    .to(super::{}::Column::{})\n    .into()",
+        module_name(parent),
+        column_variant(column, config),
+        module_name(parent),
+        column_variant(key, config)
+      ),
+    });
+  }
+  for child in tables.iter().filter(|child| child.name != table.name) {
+    let references = foreign_keys(child, tables)
+      .into_iter()
+      .filter(|(_, parent, _)| parent.name == table.name)
+      .map(|(column, _, _)| column)
+      .collect::<Vec<&ColumnData>>();
+    if let [_] = references[..] {
+      relations.push(Relation {
+        variant: enum_name(&child.name),
+        target: module_name(child),
+        attribute: Some(format!(
+          "#[sea_orm(has_many = \"super::{}::Entity\")]",
+          module_name(child)
+        )),
+        def: format!(
+          "Entity::has_many(super::{}::Entity).into()",
+          module_name(child)
+        ),
+      });
+      continue;
+    }
+    for column in references {
+      let name = column.name.strip_suffix("_id").unwrap_or(&column.name);
+      relations.push(Relation {
+        variant: enum_name(&format!("{}_{}", name, child.name)),
+        target: module_name(child),
+        attribute: None,
+        def: format!(
+          "RelationDef {{\n    rel_type: sea_orm::RelationType::HasMany,\n    ..super::{}::Relation::{}.def().rev()\n}}",
+          module_name(child),
+          enum_name(name)
+        ),
+      });
+    }
+  }
+  relations
+}
+
+// The Relation enum, derived unless a has_many has to be written out by hand
+fn relation_enum(relations: &[Relation], config: &Config) -> String {
+  if relations.is_empty() {
+    return format!(
+      "#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]\n{}enum Relation {{}}",
+      visibility(config)
+    );
+  }
+  if let Some(attributes) = relations
+    .iter()
+    .map(|relation| relation.attribute.as_ref())
+    .collect::<Option<Vec<&String>>>()
+  {
+    let variants = relations
+      .iter()
+      .zip(attributes)
+      .map(|(relation, attribute)| format!("    {}\n    {},", attribute, relation.variant))
+      .collect::<Vec<String>>();
+    return format!(
+      "#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]\n{}enum Relation {{\n{}\n}}",
+      visibility(config),
+      variants.join("\n")
+    );
+  }
+  let variants = relations
+    .iter()
+    .map(|relation| format!("    {},", relation.variant))
+    .collect::<Vec<String>>();
+  let defs = relations
+    .iter()
+    .map(|relation| {
+      let def = relation.def.replace('\n', "\n            ");
+      format!("            Self::{} => {},", relation.variant, def)
+    })
+    .collect::<Vec<String>>();
+  format!(
+    "#[derive(Copy, Clone, Debug, EnumIter)]\n{}enum Relation {{\n{}\n}}\n\nimpl RelationTrait for Relation {{\n    fn def(&self) -> RelationDef {{\n        match self {{\n{}\n        }}\n    }}\n}}",
+    visibility(config),
+    variants.join("\n"),
+    defs.join("\n")
+  )
+}
+
+fn entity(table: &Table, tables: &[&Table], config: &Config) -> String {
+  let keys = table
+    .columns
+    .iter()
+    .filter(|column| column.is_primary_key())
+    .count();
+  let fields = table.columns.iter().fold(String::new(), |fields, column| {
+    [fields, model_field(column, keys > 1, config)].join("\n")
+  });
+  let model = declaration_with(
+    "Model",
+    &fields,
+    &["DeriveEntityModel"],
+    &[format!("#[sea_orm(table_name = {:?})]", table.name)],
+    config,
+  );
+
+  let relations = relations(table, tables, config);
+  let mut items = vec![model, relation_enum(&relations, config)];
+  // Related can only be implemented once for each entity, so anything
+  // related in more than one way, or to itself, is reached with a Linked
+  let (related, linked): (Vec<&Relation>, Vec<&Relation>) =
+    relations.iter().partition(|relation| {
+      relations
+        .iter()
+        .filter(|other| other.target == relation.target)
+        .count()
+        == 1
+        && relation.target != module_name(table)
+    });
+  for relation in related {
+    items.push(format!(
+        "impl Related<super::{}::Entity> for Entity {{\n    fn to() -> RelationDef {{\n        Relation::{}.def()\n    }}\n}}",
+      relation.target, relation.variant
+    ));
+  }
+  for relation in linked {
+    items.push(format!(
+        "{}struct {}Link;\n\nimpl Linked for {}Link {{\n    type FromEntity = Entity;\n    type ToEntity = super::{}::Entity;\n\n    fn link(&self) -> Vec<RelationDef> {{\n        vec![Relation::{}.def()]\n    }}\n}}",
+        visibility(config),
+        relation.variant,
+        relation.variant,
+        relation.target,
+      relation.variant
+    ));
+  }
+  items.push("impl ActiveModelBehavior for ActiveModel {}".to_string());
+  items.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{config::Config, sea_orm::ToSeaOrm, Database};
+
+  #[test]
+  fn tables_become_entities_related_by_their_foreign_keys() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+    t.string \"name\", limit: 100, null: false
+    t.text \"bio\"
+    t.decimal \"balance\", precision: 10, scale: 2
+    t.string \"type\"
+  end
+  create_table \"posts\", force: :cascade do |t|
+    t.bigint \"author_id\", null: false
+    t.bigint \"editor_id\"
+    t.bigint \"category_id\"
+    t.jsonb \"meta\"
+    t.datetime \"created_at\", null: false
+  end
+  create_table \"categories\", force: :cascade do |t|
+    t.bigint \"parent_id\"
+  end
+  create_table \"taggings\", id: false, force: :cascade do |t|
+    t.bigint \"post_id\"
+  end
+  add_foreign_key \"posts\", \"users\", column: \"author_id\"
+  add_foreign_key \"posts\", \"users\", column: \"editor_id\"
+  add_foreign_key \"posts\", \"categories\"
+  add_foreign_key \"categories\", \"categories\", column: \"parent_id\"";
+    assert_eq!(Database::from(schema).to_sea_orm(&Config::default()),
+      "// taggings has no primary key, so it can't be a SeaORM entity
+
+pub mod users {
+    use sea_orm::entity::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, DeriveEntityModel)]
+    #[sea_orm(table_name = \"users\")]
+    pub struct Model {
+       #[sea_orm(primary_key)]
+       pub id: i64,
+       #[sea_orm(column_type = \"String(StringLen::N(100))\")]
+       pub name: String,
+       #[sea_orm(column_type = \"Text\")]
+       pub bio: Option<String>,
+       #[sea_orm(column_type = \"Decimal(Some((10, 2)))\")]
+       pub balance: Option<Decimal>,
+       pub r#type: Option<String>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter)]
+    pub enum Relation {
+        AuthorPosts,
+        EditorPosts,
+    }
+
+    impl RelationTrait for Relation {
+        fn def(&self) -> RelationDef {
+            match self {
+                Self::AuthorPosts => RelationDef {
+                    rel_type: sea_orm::RelationType::HasMany,
+                    ..super::posts::Relation::Author.def().rev()
+                },
+                Self::EditorPosts => RelationDef {
+                    rel_type: sea_orm::RelationType::HasMany,
+                    ..super::posts::Relation::Editor.def().rev()
+                },
+            }
+        }
+    }
+
+    pub struct AuthorPostsLink;
+
+    impl Linked for AuthorPostsLink {
+        type FromEntity = Entity;
+        type ToEntity = super::posts::Entity;
+
+        fn link(&self) -> Vec<RelationDef> {
+            vec![Relation::AuthorPosts.def()]
+        }
+    }
+
+    pub struct EditorPostsLink;
+
+    impl Linked for EditorPostsLink {
+        type FromEntity = Entity;
+        type ToEntity = super::posts::Entity;
+
+        fn link(&self) -> Vec<RelationDef> {
+            vec![Relation::EditorPosts.def()]
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+pub mod posts {
+    use sea_orm::entity::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, DeriveEntityModel)]
+    #[sea_orm(table_name = \"posts\")]
+    pub struct Model {
+       #[sea_orm(primary_key)]
+       pub id: i64,
+       pub author_id: i64,
+       pub editor_id: Option<i64>,
+       pub category_id: Option<i64>,
+       #[sea_orm(column_type = \"JsonBinary\")]
+       pub meta: Option<Json>,
+       pub created_at: DateTime,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(belongs_to = \"super::users::Entity\", from = \"Column::AuthorId\", to = \"super::users::Column::Id\")]
+        Author,
+        #[sea_orm(belongs_to = \"super::users::Entity\", from = \"Column::EditorId\", to = \"super::users::Column::Id\")]
+        Editor,
+        #[sea_orm(belongs_to = \"super::categories::Entity\", from = \"Column::CategoryId\", to = \"super::categories::Column::Id\")]
+        Category,
+    }
+
+    impl Related<super::categories::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Category.def()
+        }
+    }
+
+    pub struct AuthorLink;
+
+    impl Linked for AuthorLink {
+        type FromEntity = Entity;
+        type ToEntity = super::users::Entity;
+
+        fn link(&self) -> Vec<RelationDef> {
+            vec![Relation::Author.def()]
+        }
+    }
+
+    pub struct EditorLink;
+
+    impl Linked for EditorLink {
+        type FromEntity = Entity;
+        type ToEntity = super::users::Entity;
+
+        fn link(&self) -> Vec<RelationDef> {
+            vec![Relation::Editor.def()]
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+pub mod categories {
+    use sea_orm::entity::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, DeriveEntityModel)]
+    #[sea_orm(table_name = \"categories\")]
+    pub struct Model {
+       #[sea_orm(primary_key)]
+       pub id: i64,
+       pub parent_id: Option<i64>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(belongs_to = \"super::categories::Entity\", from = \"Column::ParentId\", to = \"super::categories::Column::Id\")]
+        Parent,
+        #[sea_orm(has_many = \"super::posts::Entity\")]
+        Posts,
+    }
+
+    impl Related<super::posts::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Posts.def()
+        }
+    }
+
+    pub struct ParentLink;
+
+    impl Linked for ParentLink {
+        type FromEntity = Entity;
+        type ToEntity = super::categories::Entity;
+
+        fn link(&self) -> Vec<RelationDef> {
+            vec![Relation::Parent.def()]
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+pub mod prelude {
+    pub use super::users::Entity as User;
+    pub use super::posts::Entity as Post;
+    pub use super::categories::Entity as Category;
+}"
+    );
+  }
+
+  #[test]
+  fn postgres_enums_become_active_enums() {
+    let schema = "create_enum \"mood\", [\"happy\", \"not-sure\"]
+  create_table \"people\", id: :uuid, force: :cascade do |t|
+    t.enum \"mood\", enum_type: \"mood\", null: false
+    t.datetime \"created_at\", null: false
+  end";
+    let mut config = Config::default();
+    config.rust.field_case = crate::case::Case::Camel;
+    assert_eq!(Database::from(schema).to_sea_orm(&config),
+      "pub mod sea_orm_active_enums {
+    use sea_orm::entity::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, EnumIter, DeriveActiveEnum)]
+    #[sea_orm(rs_type = \"String\", db_type = \"Enum\", enum_name = \"mood\", rename_all = \"snake_case\")]
+    #[serde(rename_all = \"snake_case\")]
+    pub enum Mood {
+        Happy,
+        #[serde(rename = \"not-sure\")]
+        #[sea_orm(string_value = \"not-sure\")]
+        NotSure,
+    }
+}
+
+pub mod people {
+    use sea_orm::entity::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, DeriveEntityModel)]
+    #[sea_orm(table_name = \"people\")]
+    pub struct Model {
+       #[sea_orm(primary_key, auto_increment = false)]
+       pub id: Uuid,
+       pub mood: super::sea_orm_active_enums::Mood,
+       #[sea_orm(column_name = \"created_at\")]
+       #[serde(rename = \"created_at\")]
+       pub createdAt: DateTime,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+pub mod prelude {
+    pub use super::people::Entity as Person;
+}"
+    );
+  }
+
+  #[test]
+  fn columns_that_are_not_identifiers_keep_their_column_name() {
+    let schema = "create_table \"codes\", force: :cascade do |t|
+    t.string \"123abc\", null: false
+    t.string \"_\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_sea_orm(&Config::default()),
+      "pub mod codes {
+    use sea_orm::entity::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, DeriveEntityModel)]
+    #[sea_orm(table_name = \"codes\")]
+    pub struct Model {
+       #[sea_orm(primary_key)]
+       pub id: i64,
+       #[sea_orm(column_name = \"123abc\")]
+       #[serde(rename = \"123abc\")]
+       pub x_123abc: String,
+       #[sea_orm(column_name = \"_\")]
+       #[serde(rename = \"_\")]
+       pub x__: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+pub mod prelude {
+    pub use super::codes::Entity as Code;
+}"
+    );
+  }
+
+  #[test]
+  fn the_output_is_valid_rust() {
+    let schema = "create_enum \"status\", [\"draft\", \"published\"]
+  create_table \"type\", force: :cascade do |t|
+    t.enum \"status\", enum_type: \"status\", default: \"draft\", null: false
+    t.enum \"statuses\", enum_type: \"status\", array: true
+    t.hstore \"settings\"
+    t.string \"123abc\"
+    t.float \"score\", null: false
+    t.time \"at\"
+    t.binary \"data\"
+    t.boolean \"active\", default: true
+    t.uuid \"token\"
+  end
+  create_table \"users\", force: :cascade do |t|
+    t.bigint \"type_id\"
+  end
+  add_foreign_key \"users\", \"type\"";
+    let sea_orm = Database::from(schema).to_sea_orm(&Config::default());
+    if let Err(error) = syn::parse_file(&sea_orm) {
+      panic!("{}\n\n{}", error, sea_orm);
+    }
+  }
+}
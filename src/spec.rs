@@ -1,49 +1,204 @@
+use serde::Deserialize;
+
+use super::case::{is_identifier, Case};
+use super::config::Config;
 use super::ColumnData;
 use super::Database;
+use super::DefaultValue;
+use super::NumericType;
 use super::RailsColumn;
 use super::Table;
 
+// Whether nullable columns are wrapped in spec/nilable
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpecNullable {
+  #[default]
+  Nilable,
+  Ignore,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct SpecConfig {
+  pub nullable: SpecNullable,
+  // Emit a `table-defaults` map for tables with literal column defaults
+  pub defaults: bool,
+}
+
+impl Default for SpecConfig {
+  fn default() -> Self {
+    SpecConfig {
+      nullable: SpecNullable::default(),
+      defaults: true,
+    }
+  }
+}
+
 pub trait ToSpec {
-  fn to_spec(&self) -> String;
+  fn to_spec(&self, config: &Config) -> String;
 }
 
 impl ToSpec for Database {
-  fn to_spec(&self) -> String {
+  fn to_spec(&self, config: &Config) -> String {
     self.0.iter().fold(String::new(), |specs, table| {
       if specs.is_empty() {
-        [specs, table.to_spec()].join("")
+        [specs, table.to_spec(config)].join("")
       } else {
-        [specs, table.to_spec()].join("\n\n")
+        [specs, table.to_spec(config)].join("\n\n")
       }
     })
   }
 }
 
+// Symbols the compiler treats specially, which can't name anything else
+const SPECIAL_FORMS: &[&str] = &[
+  "def",
+  "if",
+  "do",
+  "let",
+  "quote",
+  "var",
+  "fn",
+  "loop",
+  "recur",
+  "throw",
+  "try",
+  "catch",
+  "finally",
+  "monitor-enter",
+  "monitor-exit",
+  "new",
+  "set!",
+];
+
+// Keywords are the column's name as is, so they match the keys in the data
+// Rails sends. A name the reader won't take as a literal keyword is built
+// with `keyword` instead.
+fn keyword(name: &str) -> String {
+  let mut characters = name.chars();
+  let literal = characters
+    .next()
+    .is_some_and(|first| !first.is_ascii_digit() && is_keyword_character(first))
+    && characters.all(is_keyword_character);
+  if literal {
+    format!(":{}", name)
+  } else {
+    format!("(keyword {:?})", name)
+  }
+}
+
+fn is_keyword_character(character: char) -> bool {
+  character.is_alphanumeric() || "*+!-_'?<>=".contains(character)
+}
+
+fn symbol(name: &str, config: &Config) -> String {
+  let name = Case::Snake.convert(name, &config.acronyms);
+  if is_identifier(&name) && !SPECIAL_FORMS.contains(&name.as_str()) {
+    name
+  } else {
+    format!("_{}", name)
+  }
+}
+
 impl ToSpec for Table {
-  fn to_spec(&self) -> String {
+  fn to_spec(&self, config: &Config) -> String {
     let spec = self.columns.iter().fold(String::new(), |spec, column| {
       if spec.is_empty() {
-        spec + &column.to_spec()
+        spec + &column.to_spec(config)
       } else {
-        [spec, "   ".to_owned() + &column.to_spec()].join("\n")
+        [spec, "   ".to_owned() + &column.to_spec(config)].join("\n")
       }
     });
     // Remove last new line
-    format!("(spec/def {}\n  {{{}}})", self.name, spec)
+    let spec = format!(
+      "(spec/def {}\n  {{{}}})",
+      symbol(&self.record_name(config), config),
+      spec
+    );
+    match defaults(self, config) {
+      Some(defaults) if config.spec.defaults => [spec, defaults].join("\n\n"),
+      _ => spec,
+    }
+  }
+}
+
+// Renders a default as an EDN literal, with decimal columns' defaults as
+// BigDecimals so they pass `decimal?`
+fn edn_literal(value: &DefaultValue, numeric_type: Option<NumericType>) -> Option<String> {
+  match value {
+    DefaultValue::Boolean(boolean) => Some(boolean.to_string()),
+    DefaultValue::Integer(integer) => Some(integer.to_string()),
+    DefaultValue::Float(float) => Some(format!("{:?}", float)),
+    DefaultValue::String(number) if numeric_type == Some(NumericType::Decimal) => {
+      Some(format!("{}M", number))
+    }
+    DefaultValue::String(string) => Some(format!("{:?}", string)),
+    DefaultValue::Array(values) => {
+      let values = values
+        .iter()
+        .map(|value| edn_literal(value, numeric_type))
+        .collect::<Option<Vec<String>>>()?;
+      Some(format!("[{}]", values.join(" ")))
+    }
+    DefaultValue::Hash(pairs) => {
+      let pairs = pairs
+        .iter()
+        .map(|(key, value)| Some(format!("{:?} {}", key, edn_literal(value, None)?)))
+        .collect::<Option<Vec<String>>>()?;
+      Some(format!("{{{}}}", pairs.join(" ")))
+    }
+    DefaultValue::Expression(_) => None,
+  }
+}
+
+// Only literal defaults make it into the map, expressions are left to the database
+fn defaults(table: &Table, config: &Config) -> Option<String> {
+  let defaults = table
+    .columns
+    .iter()
+    .filter_map(|column| {
+      let literal = edn_literal(
+        &column.typed_default(config.adapter)?,
+        column.numeric_type(config.adapter),
+      )?;
+      Some(format!("{} {}", keyword(&column.name), literal))
+    })
+    .collect::<Vec<String>>();
+  if defaults.is_empty() {
+    return None;
+  }
+  Some(format!(
+    "(def {}-defaults\n  {{{}}})",
+    symbol(&table.record_name(config), config),
+    defaults.join("\n   ")
+  ))
+}
+
+impl ToSpec for NumericType {
+  fn to_spec(&self, _config: &Config) -> String {
+    match self {
+      NumericType::SmallInt | NumericType::Int | NumericType::BigInt => "int?".to_string(),
+      NumericType::Float => "float?".to_string(),
+      NumericType::Decimal => "decimal?".to_string(),
+    }
   }
 }
 
 impl ToSpec for RailsColumn {
-  fn to_spec(&self) -> String {
+  fn to_spec(&self, config: &Config) -> String {
+    if let Some(numeric_type) = self.numeric_type(config.adapter) {
+      return numeric_type.to_spec(config);
+    }
     match self {
-      RailsColumn::PrimaryKey => "int?".to_string(),
+      RailsColumn::PrimaryKey
+      | RailsColumn::Integer
+      | RailsColumn::Bigint
+      | RailsColumn::Float
+      | RailsColumn::Decimal
+      | RailsColumn::Numeric => unreachable!("numeric columns are handled above"),
       RailsColumn::String => "string?".to_string(),
       RailsColumn::Text => "string?".to_string(),
-      RailsColumn::Integer => "int?".to_string(),
-      RailsColumn::Bigint => "int?".to_string(),
-      RailsColumn::Float => "float?".to_string(),
-      RailsColumn::Decimal => "float?".to_string(),
-      RailsColumn::Numeric => "int?".to_string(),
       RailsColumn::Datetime => "string?".to_string(),
       RailsColumn::Time => "string?".to_string(),
       RailsColumn::Date => "string?".to_string(),
@@ -51,18 +206,38 @@ impl ToSpec for RailsColumn {
       RailsColumn::Boolean => "boolean?".to_string(),
       RailsColumn::HStore => "map?".to_string(),
       RailsColumn::JsonB => "map?".to_string(),
+      RailsColumn::Uuid => "uuid?".to_string(),
+      RailsColumn::Enum => "string?".to_string(),
     }
   }
 }
 impl ToSpec for ColumnData {
-  fn to_spec(&self) -> String {
-    format!(":{} {}", self.name, self.value_type.to_spec())
+  fn to_spec(&self, config: &Config) -> String {
+    let value_type = match self.numeric_type(config.adapter) {
+      Some(numeric_type) => numeric_type.to_spec(config),
+      None => self.value_type.to_spec(config),
+    };
+    let value_type = if self.options.array {
+      format!("(spec/coll-of {})", value_type)
+    } else {
+      value_type
+    };
+    match (self.nullable, config.spec.nullable) {
+      (true, SpecNullable::Nilable) => {
+        format!("{} (spec/nilable {})", keyword(&self.name), value_type)
+      }
+      _ => format!("{} {}", keyword(&self.name), value_type),
+    }
   }
 }
 
 #[cfg(test)]
 pub mod test {
-  use crate::{spec::ToSpec, Database};
+  use crate::{
+    config::Config,
+    spec::{SpecNullable, ToSpec},
+    Database,
+  };
 
   #[test]
   fn can_convert_a_rails_schema_to_a_string_version_of_a_rust_struct() {
@@ -88,25 +263,114 @@ pub mod test {
   end
 end";
     assert_eq!(
-      Database::from(schema).to_spec(),
+      Database::from(schema).to_spec(&Config::default()),
       "(spec/def sample_schema
-  {:a int?
-   :b string?
-   :c string?
-   :d int?
-   :e int?
-   :f float?
-   :g float?
-   :h int?
-   :i string?
-   :j string?
-   :k string?
-   :l string?
-   :m boolean?
-   :n map?
-   :o map?
+  {:a (spec/nilable int?)
+   :b (spec/nilable string?)
+   :c (spec/nilable string?)
+   :d (spec/nilable int?)
+   :e (spec/nilable int?)
+   :f (spec/nilable float?)
+   :g (spec/nilable decimal?)
+   :h (spec/nilable decimal?)
+   :i (spec/nilable string?)
+   :j (spec/nilable string?)
+   :k (spec/nilable string?)
+   :l (spec/nilable string?)
+   :m (spec/nilable boolean?)
+   :n (spec/nilable map?)
+   :o (spec/nilable map?)
    :created_at string?
    :updated_at string?})",
     )
   }
+
+  #[test]
+  fn nullability_can_be_ignored() {
+    let schema = "create_table \"users\", id: false, force: :cascade do |t|
+    t.string \"name\", null: false
+    t.string \"nickname\"
+  end";
+    let mut config = Config::default();
+    assert_eq!(
+      Database::from(schema).to_spec(&config),
+      "(spec/def user
+  {:name string?
+   :nickname (spec/nilable string?)})"
+    );
+
+    config.spec.nullable = SpecNullable::Ignore;
+    assert_eq!(
+      Database::from(schema).to_spec(&config),
+      "(spec/def user
+  {:name string?
+   :nickname string?})"
+    )
+  }
+
+  #[test]
+  fn literal_column_defaults_become_a_map() {
+    let schema = "create_table \"posts\", id: false, force: :cascade do |t|
+    t.string \"status\", default: \"draft\", null: false
+    t.integer \"views\", default: 0, null: false
+    t.jsonb \"settings\", default: {}, null: false
+    t.datetime \"published_at\", default: -> { \"now()\" }, null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_spec(&Config::default()),
+      "(spec/def post
+  {:status string?
+   :views int?
+   :settings map?
+   :published_at string?})
+
+(def post-defaults
+  {:status \"draft\"
+   :views 0
+   :settings {}})"
+    )
+  }
+
+  #[test]
+  fn numeric_defaults_take_the_column_type() {
+    let schema = "create_table \"accounts\", id: false, force: :cascade do |t|
+    t.decimal \"balance\", precision: 10, scale: 2, default: \"0.0\", null: false
+    t.float \"ratio\", default: 0, null: false
+    t.decimal \"amount\", precision: 9, scale: 0, default: \"5\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_spec(&Config::default()),
+      "(spec/def account
+  {:balance decimal?
+   :ratio float?
+   :amount int?})
+
+(def account-defaults
+  {:balance 0.0M
+   :ratio 0.0
+   :amount 5})"
+    )
+  }
+
+  #[test]
+  fn invalid_keywords_are_built_with_keyword() {
+    let schema = "create_table \"do\", id: false, force: :cascade do |t|
+    t.string \"type\", null: false
+    t.string \"self\", null: false
+    t.integer \"range\", null: false
+    t.string \"123abc\", null: false
+    t.string \"foo-bar\", null: false
+    t.string \"_\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_spec(&Config::default()),
+      "(spec/def _do
+  {:type string?
+   :self string?
+   :range int?
+   (keyword \"123abc\") string?
+   :foo-bar string?
+   :_ string?})"
+    )
+  }
 }
@@ -0,0 +1,581 @@
+use std::collections::BTreeSet;
+
+use super::case::{is_identifier, Case};
+use super::config::{Adapter, Config};
+use super::typescript::{field_name, key, property_name};
+use super::ColumnData;
+use super::Database;
+use super::DefaultValue;
+use super::Index;
+use super::NumericType;
+use super::RailsColumn;
+use super::Table;
+
+pub trait ToTypeOrm {
+  // Each file's name along with its contents
+  fn to_typeorm(&self, config: &Config) -> Vec<(String, String)>;
+}
+
+// One file per entity, named like `typeorm entity:create` names them, and an
+// index.ts that re-exports every entity
+impl ToTypeOrm for Database {
+  fn to_typeorm(&self, config: &Config) -> Vec<(String, String)> {
+    let relations = relations(self);
+    let (tables, skipped): (Vec<&Table>, Vec<&Table>) =
+      self.0.iter().partition(|table| has_primary_key(table));
+    let mut files = tables
+      .iter()
+      .map(|table| (file_name(table, config), entity(table, &relations, config)))
+      .collect::<Vec<(String, String)>>();
+
+    // TypeORM can't describe a table without a primary key
+    let mut index = skipped
+      .iter()
+      .map(|table| {
+        format!(
+          "// {} has no primary key, so it can't be a TypeORM entity",
+          table.name
+        )
+      })
+      .collect::<Vec<String>>();
+    index.extend(
+      tables
+        .iter()
+        .map(|table| format!("export * from \"./{}\";", module_name(table, config))),
+    );
+    files.push(("index.ts".to_string(), index.join("\n")));
+    files
+  }
+}
+
+fn module_name(table: &Table, config: &Config) -> String {
+  format!(
+    "{}.entity",
+    Case::Kebab.convert(&table.record_name(config), &[])
+  )
+}
+
+fn file_name(table: &Table, config: &Config) -> String {
+  format!("{}.ts", module_name(table, config))
+}
+
+fn has_primary_key(table: &Table) -> bool {
+  table.columns.iter().any(ColumnData::is_primary_key)
+}
+
+// The name a row of the table goes by in a relation's inverse side
+fn parameter(table: &Table, config: &Config) -> String {
+  let name = Case::Camel.convert(&table.record_name(config), &config.acronyms);
+  if key(name.clone()) == name {
+    name
+  } else {
+    "row".to_string()
+  }
+}
+
+// Reads a property off an entity, which can't always be done with a dot
+fn member(object: &str, property: &str) -> String {
+  if is_identifier(property) {
+    format!("{}.{}", object, property)
+  } else {
+    format!("{}[{:?}]", object, property)
+  }
+}
+
+fn property(name: &str, config: &Config) -> String {
+  property_name(name, config)
+}
+
+// A foreign key between two entities, named like a belongs_to on the child
+// and a has_many on the parent
+struct Relation<'a> {
+  child: &'a Table,
+  column: &'a ColumnData,
+  parent: &'a Table,
+  belongs_to: String,
+  has_many: String,
+}
+
+fn relations(database: &Database) -> Vec<Relation<'_>> {
+  let relations = database
+    .0
+    .iter()
+    .filter(|child| has_primary_key(child))
+    .flat_map(|child| {
+      child.columns.iter().filter_map(move |column| {
+        let foreign_key = column.options.foreign_key.as_ref()?;
+        let parent = database
+          .0
+          .iter()
+          .find(|parent| parent.name == foreign_key.table)?;
+        let references = parent
+          .columns
+          .iter()
+          .find(|key| key.name == foreign_key.primary_key)?;
+        if !references.is_primary_key() {
+          return None;
+        }
+        Some((child, column, parent, column.name.strip_suffix("_id")?))
+      })
+    })
+    .collect::<Vec<(&Table, &ColumnData, &Table, &str)>>();
+  relations
+    .iter()
+    .map(|(child, column, parent, name)| {
+      // Two foreign keys from the same table need telling apart, like Rails'
+      // has_many :posts_as_author
+      let ambiguous = relations
+        .iter()
+        .filter(|(other, _, to, _)| other.name == child.name && to.name == parent.name)
+        .count()
+        > 1;
+      let has_many = if ambiguous {
+        format!("{}_as_{}", child.name, name)
+      } else {
+        child.name.clone()
+      };
+      Relation {
+        child,
+        column,
+        parent,
+        belongs_to: name.to_string(),
+        has_many,
+      }
+    })
+    .collect()
+}
+
+fn entity(table: &Table, relations: &[Relation], config: &Config) -> String {
+  let name = table.type_name(config);
+  let mut decorators = BTreeSet::from(["Entity"]);
+  let mut imports = BTreeSet::new();
+  let mut members = vec![];
+
+  for column in &table.columns {
+    let (decorator, options) = column_decorator(column, config);
+    decorators.insert(decorator);
+    members.push(format!(
+      "  @{}({})\n  {}!: {};",
+      decorator,
+      options,
+      field_name(&column.name, config),
+      property_type(column, config)
+    ));
+  }
+
+  for relation in relations {
+    if relation.child.name == table.name {
+      let parent = relation.parent.type_name(config);
+      let inverse = parameter(relation.parent, config);
+      // TypeORM treats a relation as nullable unless told otherwise
+      let optional = if relation.column.nullable {
+        ""
+      } else {
+        ", { nullable: false }"
+      };
+      decorators.extend(["ManyToOne", "JoinColumn"]);
+      members.push(format!(
+        "  @ManyToOne(() => {}, ({}) => {}{})\n  @JoinColumn({{ name: {:?} }})\n  {}!: Relation<{}>{};",
+        parent,
+        inverse,
+        member(&inverse, &property(&relation.has_many, config)),
+        optional,
+        relation.column.name,
+        field_name(&relation.belongs_to, config),
+        parent,
+        if relation.column.nullable { " | null" } else { "" }
+      ));
+      if relation.parent.name != table.name {
+        imports.insert((parent, module_name(relation.parent, config)));
+      }
+    }
+    if relation.parent.name == table.name {
+      let child = relation.child.type_name(config);
+      let inverse = parameter(relation.child, config);
+      decorators.insert("OneToMany");
+      members.push(format!(
+        "  @OneToMany(() => {}, ({}) => {})\n  {}!: Relation<{}>[];",
+        child,
+        inverse,
+        member(&inverse, &property(&relation.belongs_to, config)),
+        field_name(&relation.has_many, config),
+        child
+      ));
+      if relation.child.name != table.name {
+        imports.insert((child, module_name(relation.child, config)));
+      }
+    }
+  }
+
+  let mut class_decorators = vec![format!("@Entity({:?})", table.name)];
+  for index in &table.indexes {
+    let decorator = index_decorator(index, table, config);
+    if decorator.starts_with('@') {
+      decorators.insert("Index");
+    }
+    class_decorators.push(decorator);
+  }
+
+  let mut import_lines = vec![];
+  let uses_relations = members.iter().any(|member| member.contains("Relation<"));
+  let typeorm = decorators
+    .into_iter()
+    .chain(uses_relations.then_some("type Relation"))
+    .collect::<Vec<&str>>();
+  import_lines.push(format!(
+    "import {{ {} }} from \"typeorm\";",
+    typeorm.join(", ")
+  ));
+  import_lines.extend(
+    imports
+      .into_iter()
+      .map(|(name, module)| format!("import {{ {} }} from \"./{}\";", name, module)),
+  );
+
+  format!(
+    "{}\n\n{}\nexport class {} {{\n{}\n}}",
+    import_lines.join("\n"),
+    class_decorators.join("\n"),
+    name,
+    members.join("\n\n")
+  )
+}
+
+// Indexes on an expression rather than columns are left as a comment
+fn index_decorator(index: &Index, table: &Table, config: &Config) -> String {
+  let name = index
+    .name
+    .clone()
+    .unwrap_or_else(|| format!("index_{}_on_{}", table.name, index.columns.join("_and_")));
+  let known = index
+    .columns
+    .iter()
+    .all(|name| table.columns.iter().any(|column| &column.name == name));
+  if !known {
+    return format!("// {} is on an expression TypeORM can't describe", name);
+  }
+  let columns = index
+    .columns
+    .iter()
+    .map(|column| format!("{:?}", property(column, config)))
+    .collect::<Vec<String>>()
+    .join(", ");
+  if index.unique {
+    format!("@Index({:?}, [{}], {{ unique: true }})", name, columns)
+  } else {
+    format!("@Index({:?}, [{}])", name, columns)
+  }
+}
+
+// The column type as TypeORM names it for the adapter's driver
+fn column_type(column: &ColumnData, config: &Config) -> &'static str {
+  let adapter = config.adapter;
+  match (&column.value_type, adapter) {
+    (RailsColumn::Enum, _) => "enum",
+    (RailsColumn::Decimal | RailsColumn::Numeric, Adapter::Postgresql) => "numeric",
+    (RailsColumn::Decimal | RailsColumn::Numeric, _) => "decimal",
+    (RailsColumn::String, Adapter::Postgresql) => "character varying",
+    (RailsColumn::String, _) => "varchar",
+    (RailsColumn::Text, _) => "text",
+    (RailsColumn::Datetime, Adapter::Postgresql) => "timestamp",
+    (RailsColumn::Datetime, _) => "datetime",
+    (RailsColumn::Date, _) => "date",
+    (RailsColumn::Time, _) => "time",
+    (RailsColumn::Binary, Adapter::Postgresql) => "bytea",
+    (RailsColumn::Binary, _) => "blob",
+    (RailsColumn::Boolean, _) => "boolean",
+    (RailsColumn::HStore, _) => "hstore",
+    (RailsColumn::JsonB, Adapter::Postgresql) => "jsonb",
+    (RailsColumn::JsonB, Adapter::Mysql) => "json",
+    (RailsColumn::JsonB, Adapter::Sqlite) => "simple-json",
+    (RailsColumn::Uuid, Adapter::Postgresql) => "uuid",
+    (RailsColumn::Uuid, _) => "varchar",
+    (_, Adapter::Sqlite) => match column.numeric_type(adapter) {
+      Some(NumericType::Float) => "real",
+      _ => "integer",
+    },
+    _ => match column.numeric_type(adapter) {
+      Some(NumericType::SmallInt) => "smallint",
+      Some(NumericType::BigInt) => "bigint",
+      Some(NumericType::Float) if adapter == Adapter::Postgresql => "double precision",
+      Some(NumericType::Float) => "float",
+      _ if adapter == Adapter::Postgresql => "integer",
+      _ => "int",
+    },
+  }
+}
+
+fn allowed_values(column: &ColumnData) -> &[String] {
+  match &column.options.enum_type {
+    Some(enum_type) if !enum_type.values.is_empty() => &enum_type.values,
+    _ => &column.options.allowed_values,
+  }
+}
+
+fn values(values: &[String], separator: &str) -> String {
+  values
+    .iter()
+    .map(|value| format!("{:?}", value))
+    .collect::<Vec<String>>()
+    .join(separator)
+}
+
+// What the driver reads the column as. Drivers hand back bigints and
+// decimals as strings so they don't lose precision.
+fn property_type(column: &ColumnData, config: &Config) -> String {
+  let allowed_values = allowed_values(column);
+  let property_type = if !allowed_values.is_empty() {
+    values(allowed_values, " | ")
+  } else {
+    match (column_type(column, config), config.adapter) {
+      ("bigint", Adapter::Sqlite) => "number",
+      ("bigint" | "numeric" | "decimal", _) => "string",
+      ("smallint" | "integer" | "int" | "real" | "float" | "double precision", _) => "number",
+      ("timestamp" | "datetime", _) => "Date",
+      ("bytea" | "blob", _) => "Buffer",
+      ("boolean", _) => "boolean",
+      ("hstore", _) => "Record<string, string>",
+      ("jsonb" | "json" | "simple-json", _) => "any",
+      _ => "string",
+    }
+    .to_string()
+  };
+  let property_type = match (column.options.array, allowed_values.is_empty()) {
+    (true, true) => format!("{}[]", property_type),
+    (true, false) => format!("({})[]", property_type),
+    (false, _) => property_type,
+  };
+  if column.nullable {
+    format!("{} | null", property_type)
+  } else {
+    property_type
+  }
+}
+
+// A default TypeORM can write into the table definition. Anything but a plain
+// literal is given as the SQL for it.
+fn default(value: &DefaultValue, column: &ColumnData) -> Option<String> {
+  let sql = match value {
+    DefaultValue::Expression(expression) => expression.clone(),
+    DefaultValue::Array(values) => {
+      let values = values
+        .iter()
+        .map(|value| match value {
+          DefaultValue::String(string) => Some(string.clone()),
+          value => value.to_json(),
+        })
+        .collect::<Option<Vec<String>>>()?;
+      format!("'{{{}}}'", values.join(","))
+    }
+    DefaultValue::Hash(_) => format!("'{}'", value.to_json()?.replace('\'', "''")),
+    DefaultValue::String(_)
+      if matches!(
+        column.value_type,
+        RailsColumn::Decimal | RailsColumn::Numeric
+      ) =>
+    {
+      return value.as_number().map(|number| format!("{:?}", number));
+    }
+    value => return value.to_json(),
+  };
+  Some(format!("() => {:?}", sql))
+}
+
+// Rails' own timestamps are filled in by TypeORM's date columns
+fn column_decorator(column: &ColumnData, config: &Config) -> (&'static str, String) {
+  let options = &column.options;
+  let column_type = column_type(column, config);
+  let mut arguments = vec![];
+  let property = property(&column.name, config);
+  if property != column.name {
+    arguments.push(format!("name: {:?}", column.name));
+  }
+
+  let generated = column.is_primary_key()
+    && match (&column.value_type, &options.default) {
+      (RailsColumn::PrimaryKey | RailsColumn::Integer | RailsColumn::Bigint, None) => true,
+      (RailsColumn::Uuid, Some(DefaultValue::Expression(expression))) => {
+        matches!(
+          expression.as_str(),
+          "gen_random_uuid()" | "uuid_generate_v4()"
+        )
+      }
+      _ => false,
+    };
+  if generated && column.value_type == RailsColumn::Uuid {
+    let arguments = if arguments.is_empty() {
+      "\"uuid\"".to_string()
+    } else {
+      format!("\"uuid\", {{ {} }}", arguments.join(", "))
+    };
+    return ("PrimaryGeneratedColumn", arguments);
+  }
+
+  arguments.push(format!("type: {:?}", column_type));
+  if generated {
+    return (
+      "PrimaryGeneratedColumn",
+      format!("{{ {} }}", arguments.join(", ")),
+    );
+  }
+  if let Some(limit) = options
+    .limit
+    .filter(|_| column_type.ends_with("varying") || column_type == "varchar")
+  {
+    arguments.push(format!("length: {}", limit));
+  }
+  if let Some(precision) = options.precision {
+    arguments.push(format!("precision: {}", precision));
+  }
+  if let Some(scale) = options.scale {
+    arguments.push(format!("scale: {}", scale));
+  }
+  if options.array {
+    arguments.push("array: true".to_string());
+  }
+  if column_type == "enum" {
+    let values = values(allowed_values(column), ", ");
+    arguments.push(format!("enum: [{}]", values));
+    if let Some(enum_type) = &options.enum_type {
+      arguments.push(format!("enumName: {:?}", enum_type.name));
+    }
+  }
+  if column.nullable {
+    arguments.push("nullable: true".to_string());
+  }
+  let timestamp = matches!(column_type, "timestamp" | "datetime");
+  let decorator = match column.name.as_str() {
+    _ if column.is_primary_key() => "PrimaryColumn",
+    "created_at" | "created_on" if timestamp => "CreateDateColumn",
+    "updated_at" | "updated_on" if timestamp => "UpdateDateColumn",
+    _ => "Column",
+  };
+  if let Some(default) = options
+    .default
+    .as_ref()
+    .and_then(|value| default(value, column))
+  {
+    arguments.push(format!("default: {}", default));
+  }
+  (decorator, format!("{{ {} }}", arguments.join(", ")))
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{config::Config, typeorm::ToTypeOrm, Database};
+
+  #[test]
+  fn each_table_becomes_an_entity_file_with_a_barrel() {
+    let schema = "create_enum \"mood\", [\"happy\", \"sad\"]
+  create_table \"users\", force: :cascade do |t|
+    t.string \"email\", limit: 255, null: false
+    t.enum \"mood\", enum_type: \"mood\", default: \"happy\", null: false
+    t.decimal \"balance\", precision: 10, scale: 2, default: \"0.0\", null: false
+    t.jsonb \"settings\", default: {}, null: false
+    t.datetime \"created_at\", null: false
+    t.datetime \"updated_at\", null: false
+    t.index [\"email\"], name: \"index_users_on_email\", unique: true
+  end
+  create_table \"blog_posts\", id: :uuid, default: -> { \"gen_random_uuid()\" }, force: :cascade do |t|
+    t.bigint \"user_id\"
+    t.string \"tags\", array: true, default: [], null: false
+  end
+  create_table \"taggings\", id: false, force: :cascade do |t|
+    t.bigint \"tag_id\"
+  end
+  add_foreign_key \"blog_posts\", \"users\"";
+    let files = Database::from(schema).to_typeorm(&Config::default());
+    assert_eq!(
+      files
+        .iter()
+        .map(|(name, contents)| format!("// {}\n{}", name, contents))
+        .collect::<Vec<String>>()
+        .join("\n\n"),
+      "// user.entity.ts
+import { Column, CreateDateColumn, Entity, Index, OneToMany, PrimaryGeneratedColumn, UpdateDateColumn, type Relation } from \"typeorm\";
+import { BlogPost } from \"./blog-post.entity\";
+
+@Entity(\"users\")
+@Index(\"index_users_on_email\", [\"email\"], { unique: true })
+export class User {
+  @PrimaryGeneratedColumn({ type: \"bigint\" })
+  id!: string;
+
+  @Column({ type: \"character varying\", length: 255 })
+  email!: string;
+
+  @Column({ type: \"enum\", enum: [\"happy\", \"sad\"], enumName: \"mood\", default: \"happy\" })
+  mood!: \"happy\" | \"sad\";
+
+  @Column({ type: \"numeric\", precision: 10, scale: 2, default: \"0.0\" })
+  balance!: string;
+
+  @Column({ type: \"jsonb\", default: () => \"'{}'\" })
+  settings!: any;
+
+  @CreateDateColumn({ type: \"timestamp\" })
+  created_at!: Date;
+
+  @UpdateDateColumn({ type: \"timestamp\" })
+  updated_at!: Date;
+
+  @OneToMany(() => BlogPost, (blogPost) => blogPost.user)
+  blog_posts!: Relation<BlogPost>[];
+}
+
+// blog-post.entity.ts
+import { Column, Entity, JoinColumn, ManyToOne, PrimaryGeneratedColumn, type Relation } from \"typeorm\";
+import { User } from \"./user.entity\";
+
+@Entity(\"blog_posts\")
+export class BlogPost {
+  @PrimaryGeneratedColumn(\"uuid\")
+  id!: string;
+
+  @Column({ type: \"bigint\", nullable: true })
+  user_id!: string | null;
+
+  @Column({ type: \"character varying\", array: true, default: () => \"'{}'\" })
+  tags!: string[];
+
+  @ManyToOne(() => User, (user) => user.blog_posts)
+  @JoinColumn({ name: \"user_id\" })
+  user!: Relation<User> | null;
+}
+
+// index.ts
+// taggings has no primary key, so it can't be a TypeORM entity
+export * from \"./user.entity\";
+export * from \"./blog-post.entity\";"
+    );
+  }
+
+  #[test]
+  fn the_output_is_valid_typescript() {
+    let schema = "create_enum \"status\", [\"draft\", \"published\"]
+  create_table \"2fa_codes\", force: :cascade do |t|
+    t.enum \"status\", enum_type: \"status\", array: true, null: false
+    t.hstore \"settings\"
+    t.string \"default\", limit: 10
+    t.string \"123abc\"
+    t.text \"kind\"
+    t.time \"at\"
+    t.bigint \"parent_id\"
+    t.bigint \"other_parent_id\"
+    t.datetime \"created_at\", default: -> { \"CURRENT_TIMESTAMP\" }, null: false
+    t.index \"lower((kind)::text)\", name: \"index_codes_on_lower_kind\"
+    t.index [\"default\", \"123abc\"]
+    t.check_constraint \"kind IN ('a', 'b')\"
+  end
+  add_foreign_key \"2fa_codes\", \"2fa_codes\", column: \"parent_id\"
+  add_foreign_key \"2fa_codes\", \"2fa_codes\", column: \"other_parent_id\"";
+    for (name, contents) in Database::from(schema).to_typeorm(&Config::default()) {
+      let allocator = oxc_allocator::Allocator::default();
+      let parsed =
+        oxc_parser::Parser::new(&allocator, &contents, oxc_span::SourceType::ts()).parse();
+      if let Some(error) = parsed.errors.first() {
+        panic!("{}\n\n{}\n{}", error, name, contents);
+      }
+    }
+  }
+}
@@ -1,79 +1,908 @@
 
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+
 use crate::ColumnData;
 
+use super::case::{is_identifier, Case};
+use super::config::{Config, VariantConfig};
+use super::Association;
+use super::AssociationKind;
 use super::Database;
+use super::DefaultValue;
+use super::NumericType;
 use super::RailsColumn;
 use super::Table;
+use super::Variant;
+
+// How decimal columns are represented in TypeScript. Rails serialises
+// BigDecimal as a string so that is the safest default.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TypeScriptDecimal {
+  #[default]
+  String,
+  Big,
+  Number,
+}
+
+// How datetime, date and time columns are represented in TypeScript.
+// `Date` has no notion of a time of day, so time columns stay strings.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TypeScriptDateTime {
+  #[default]
+  String,
+  Date,
+  Branded,
+  Temporal,
+}
+
+// How nullable columns are represented in TypeScript. Rails always
+// includes the key in its JSON, so a nullable column is `T | null` by default.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TypeScriptNullable {
+  #[default]
+  Null,
+  Undefined,
+  Optional,
+  OptionalNull,
+}
+
+// Whether tables are declared as object types or as interfaces
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TypeScriptDeclaration {
+  #[default]
+  Type,
+  Interface,
+}
+
+// Keys that can be marked readonly, as they shouldn't change once a row exists
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TypeScriptReadonly {
+  PrimaryKeys,
+  Timestamps,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TypeScriptConfig {
+  pub declaration: TypeScriptDeclaration,
+  pub readonly: Vec<TypeScriptReadonly>,
+  // Write a .d.ts file, where functions and constants are declared without a body
+  pub dts: bool,
+  pub decimal: TypeScriptDecimal,
+  pub date_time: TypeScriptDateTime,
+  pub nullable: TypeScriptNullable,
+  // Emit a `defaultTable()` factory for tables with column defaults
+  pub defaults: bool,
+  // Emit `isTable` type guards and `decodeTable` functions that check JSON at runtime
+  pub guards: bool,
+  pub variants: VariantConfig,
+  // Add optional fields for eager loaded belongs_to and has_many associations
+  pub associations: bool,
+  // The case of each key. Anything but snake_case also emits a map from
+  // each key to the JSON key Rails uses, for `fromJson` and `toJson`.
+  pub field_case: Case,
+  // Brand each table's primary key as a type like `UserId`, used for it and
+  // every foreign key pointing at it
+  pub id_types: bool,
+}
+
+impl Default for TypeScriptConfig {
+  fn default() -> Self {
+    TypeScriptConfig {
+      declaration: TypeScriptDeclaration::default(),
+      readonly: vec![],
+      dts: false,
+      decimal: TypeScriptDecimal::default(),
+      date_time: TypeScriptDateTime::default(),
+      nullable: TypeScriptNullable::default(),
+      defaults: true,
+      guards: false,
+      variants: VariantConfig::default(),
+      associations: false,
+      field_case: Case::Snake,
+      id_types: false,
+    }
+  }
+}
+
+// Imports and declarations a generated type can depend on, keyed by the
+// identifier that pulls them in
+const PRELUDE: &[(&str, &str)] = &[
+  ("Big", "import Big from \"big.js\";"),
+  (
+    "Temporal",
+    "import { Temporal } from \"@js-temporal/polyfill\";",
+  ),
+  (
+    "IsoDateTime",
+    "export type IsoDateTime = string & { readonly __brand: \"IsoDateTime\" };",
+  ),
+  (
+    "IsoDate",
+    "export type IsoDate = string & { readonly __brand: \"IsoDate\" };",
+  ),
+  (
+    "IsoTime",
+    "export type IsoTime = string & { readonly __brand: \"IsoTime\" };",
+  ),
+];
+
+fn prelude(database: &Database, config: &Config) -> String {
+  let identifiers = database
+    .0
+    .iter()
+    .flat_map(|table| table.columns.iter())
+    .map(|column| value_type(column, &[], config))
+    .flat_map(|value_type| {
+      value_type
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .map(str::to_string)
+        .collect::<Vec<String>>()
+    })
+    .collect::<BTreeSet<String>>();
+  PRELUDE
+    .iter()
+    .filter(|(identifier, _)| identifiers.contains(*identifier))
+    .map(|(_, line)| *line)
+    .collect::<Vec<&str>>()
+    .join("\n")
+}
+
+// Converts between the generated types and the JSON Rails sends, using a
+// table's key map
+const KEY_HELPERS: &str = "export function fromJson<T>(keys: Record<keyof T, string>, json: Record<string, unknown>): T {
+  const result: Record<string, unknown> = {};
+  for (const [key, jsonKey] of Object.entries<string>(keys)) {
+    if (jsonKey in json) result[key] = json[jsonKey];
+  }
+  return result as T;
+}
+
+export function toJson<T extends object>(keys: Record<keyof T, string>, value: T): Record<string, unknown> {
+  const result: Record<string, unknown> = {};
+  for (const [key, jsonKey] of Object.entries<string>(keys)) {
+    if (key in value) result[jsonKey] = (value as Record<string, unknown>)[key];
+  }
+  return result;
+}";
+
+// The key helpers' signatures, for a .d.ts file
+const KEY_HELPER_DECLARATIONS: &str = "export declare function fromJson<T>(keys: Record<keyof T, string>, json: Record<string, unknown>): T;
+export declare function toJson<T extends object>(keys: Record<keyof T, string>, value: T): Record<string, unknown>;";
 
 pub trait ToTypeScript {
-  fn to_typescript(&self) -> String;
+  fn to_typescript(&self, config: &Config) -> String;
 }
 
 impl ToTypeScript for Database {
-  fn to_typescript(&self) -> String {
-    self.0.iter().fold(String::new(), |specs, table| {
-      if specs.is_empty() {
-        [specs, table.to_typescript()].join("")
+  fn to_typescript(&self, config: &Config) -> String {
+    let mut header = prelude(self, config);
+    let associations = self
+      .0
+      .iter()
+      .map(|table| {
+        if config.typescript.associations {
+          self.associations(table)
+        } else {
+          vec![]
+        }
+      })
+      .collect::<Vec<Vec<Association>>>();
+    let renames_keys = self
+      .0
+      .iter()
+      .zip(&associations)
+      .any(|(table, associations)| renames_keys(table, associations, config));
+    if renames_keys {
+      let helpers = if config.typescript.dts {
+        KEY_HELPER_DECLARATIONS
       } else {
-        [specs, table.to_typescript()].join("\n\n")
-      }
-    })
+        KEY_HELPERS
+      };
+      header = [header, helpers.to_string()]
+        .into_iter()
+        .filter(|section| !section.is_empty())
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    }
+    self
+      .0
+      .iter()
+      .zip(&associations)
+      .fold(header, |specs, (table, associations)| {
+        let keys = if config.typescript.id_types {
+          self.keys(table)
+        } else {
+          vec![]
+        };
+        if specs.is_empty() {
+          [
+            specs,
+            table_to_typescript(table, associations, &keys, config),
+          ]
+          .join("")
+        } else {
+          [
+            specs,
+            table_to_typescript(table, associations, &keys, config),
+          ]
+          .join("\n\n")
+        }
+      })
+  }
+}
+
+// Reserved words in JavaScript and strict mode TypeScript. They're allowed
+// as keys, but quoting them keeps every tool reading the output happy.
+const RESERVED_WORDS: &[&str] = &[
+  "break",
+  "case",
+  "catch",
+  "class",
+  "const",
+  "continue",
+  "debugger",
+  "default",
+  "delete",
+  "do",
+  "else",
+  "enum",
+  "export",
+  "extends",
+  "false",
+  "finally",
+  "for",
+  "function",
+  "if",
+  "import",
+  "in",
+  "instanceof",
+  "new",
+  "null",
+  "return",
+  "super",
+  "switch",
+  "this",
+  "throw",
+  "true",
+  "try",
+  "typeof",
+  "var",
+  "void",
+  "while",
+  "with",
+  "implements",
+  "interface",
+  "let",
+  "package",
+  "private",
+  "protected",
+  "public",
+  "static",
+  "yield",
+];
+
+// Keys are quoted when they aren't a plain identifier
+pub(crate) fn key(name: String) -> String {
+  if is_identifier(&name) && !RESERVED_WORDS.contains(&name.as_str()) {
+    name
+  } else {
+    format!("\"{}\"", name)
   }
 }
-fn format_name(name: &str) -> String {
-  name
-    .split('_')
-    .map(|n| n.chars().next().unwrap().to_uppercase().to_string() + &n[1..])
-    .collect::<Vec<String>>()
-    .join("")
+
+// Properties are named after their column as it is, unless they're put in
+// another case
+pub(crate) fn property_name(name: &str, config: &Config) -> String {
+  match config.typescript.field_case {
+    Case::Snake => name.to_string(),
+    case => case.convert(name, &config.acronyms),
+  }
 }
 
+// A property's name as a key, quoted when it isn't an identifier
+pub(crate) fn field_name(name: &str, config: &Config) -> String {
+  key(property_name(name, config))
+}
+
+// A table on its own doesn't know what points at it, so it has no
+// associations, and its keys keep their own types
 impl ToTypeScript for Table {
-  fn to_typescript(&self) -> String {
-    let spec = self.columns.iter().fold(String::new(), |spec, column| {
-      [spec, "   ".to_owned() + &column.to_typescript()].join("\n")
+  fn to_typescript(&self, config: &Config) -> String {
+    table_to_typescript(self, &[], &[], config)
+  }
+}
+
+// The branded type of the key a column holds, if it holds one
+fn id_type(column: &ColumnData, keys: &[(&ColumnData, &Table)], config: &Config) -> Option<String> {
+  keys
+    .iter()
+    .find(|(key, _)| key.name == column.name)
+    .map(|(_, table)| format!("{}Id", table.type_name(config)))
+}
+
+// Whether any of the type's keys differ from the key Rails uses in JSON,
+// and so need mapping back to it
+fn renames_keys(table: &Table, associations: &[Association], config: &Config) -> bool {
+  table
+    .columns
+    .iter()
+    .map(|column| &column.name)
+    .chain(associations.iter().map(|association| &association.name))
+    .any(|name| property_name(name, config) != *name)
+}
+
+// Maps each key of the type to the key Rails uses for it in JSON
+fn key_map(table: &Table, associations: &[Association], name: &str, config: &Config) -> String {
+  let names = table
+    .columns
+    .iter()
+    .map(|column| &column.name)
+    .chain(associations.iter().map(|association| &association.name));
+  let keys = names.fold(String::new(), |keys, json_key| {
+    [
+      keys,
+      format!("  {}: {:?},", field_name(json_key, config), json_key),
+    ]
+    .join("\n")
+  });
+  if config.typescript.dts {
+    return format!(
+      "export declare const {}Keys: Record<keyof {}, string>;",
+      name, name
+    );
+  }
+  format!(
+    "export const {}Keys: Record<keyof {}, string> = {{{}\n}};",
+    name, name, keys
+  )
+}
+
+// An exported object type or interface with the given fields
+fn declaration(name: &str, spec: &str, config: &Config) -> String {
+  match config.typescript.declaration {
+    TypeScriptDeclaration::Type => format!("export type {} = {{{}\n}};", name, spec),
+    TypeScriptDeclaration::Interface => format!("export interface {} {{{}\n}}", name, spec),
+  }
+}
+
+// Primary keys and timestamps can be marked readonly on the table's own type
+fn is_readonly(column: &ColumnData, config: &Config) -> bool {
+  let readonly = &config.typescript.readonly;
+  (column.is_primary_key() && readonly.contains(&TypeScriptReadonly::PrimaryKeys))
+    || (column.is_timestamp() && readonly.contains(&TypeScriptReadonly::Timestamps))
+}
+
+// Associations are only present when the API eager loads them
+fn association_field(association: &Association, config: &Config) -> String {
+  let name = association.table.type_name(config);
+  match association.kind {
+    AssociationKind::BelongsTo => format!("{}?: {};", field_name(&association.name, config), name),
+    AssociationKind::HasMany => format!("{}?: {}[];", field_name(&association.name, config), name),
+  }
+}
+
+fn table_to_typescript(
+  table: &Table,
+  associations: &[Association],
+  keys: &[(&ColumnData, &Table)],
+  config: &Config,
+) -> String {
+  let spec = table.columns.iter().fold(String::new(), |spec, column| {
+    let modifier = if is_readonly(column, config) {
+      "readonly "
+    } else {
+      ""
+    };
+    [
+      spec,
+      "   ".to_owned() + modifier + &column_field(column, keys, config),
+    ]
+    .join("\n")
+  });
+  let spec = associations.iter().fold(spec, |spec, association| {
+    [
+      spec,
+      "   ".to_owned() + &association_field(association, config),
+    ]
+    .join("\n")
+  });
+
+  let name = table.type_name(config);
+  let mut items = vec![];
+  // Brands only exist for the type checker, so they're the key's own type
+  // as far as JSON and the database are concerned
+  let own_key = keys
+    .iter()
+    .find(|(column, owner)| owner.name == table.name && column.options.foreign_key.is_none());
+  if let Some((key, _)) = own_key {
+    let id_type = format!("{}Id", name);
+    items.push(format!(
+      "export type {} = {} & {{ readonly __brand: {:?} }};",
+      id_type,
+      value_type(key, &[], config),
+      id_type
+    ));
+  }
+  items.push(declaration(&name, &spec, config));
+  if renames_keys(table, associations, config) {
+    items.push(key_map(table, associations, &name, config));
+  }
+  if config.typescript.defaults && table.has_literal_defaults() {
+    items.push(default_factory(table, &name, keys, config));
+  }
+  if config.typescript.guards {
+    items.push(guards(table, &name, keys, config));
+  }
+  if config.typescript.variants.enabled {
+    items.push(variant(table, &name, Variant::Insert, keys, config));
+    items.push(variant(table, &name, Variant::Patch, keys, config));
+  }
+  items.join("\n\n")
+}
+
+// Columns that can be left out become optional keys, nullable ones still accept null
+fn variant_field(
+  column: &ColumnData,
+  optional: bool,
+  keys: &[(&ColumnData, &Table)],
+  config: &Config,
+) -> String {
+  if !optional {
+    return column_field(column, keys, config);
+  }
+  let value_type = value_type(column, keys, config);
+  match (column.nullable, config.typescript.nullable) {
+    (false, _) | (true, TypeScriptNullable::Optional) => {
+      format!("{}?: {};", field_name(&column.name, config), value_type)
+    }
+    (true, TypeScriptNullable::Undefined) => {
+      format!(
+        "{}?: {} | undefined;",
+        field_name(&column.name, config),
+        value_type
+      )
+    }
+    (true, _) => format!(
+      "{}?: {} | null;",
+      field_name(&column.name, config),
+      value_type
+    ),
+  }
+}
+
+fn variant(
+  table: &Table,
+  name: &str,
+  variant: Variant,
+  keys: &[(&ColumnData, &Table)],
+  config: &Config,
+) -> String {
+  let spec =
+    table
+      .variant_columns(variant)
+      .into_iter()
+      .fold(String::new(), |spec, (column, optional)| {
+        [
+          spec,
+          "   ".to_owned() + &variant_field(column, optional, keys, config),
+        ]
+        .join("\n")
+      });
+  declaration(
+    &config.typescript.variants.name(variant, name),
+    &spec,
+    config,
+  )
+}
+
+// Renders a literal default as a value of the column's TypeScript type
+fn default_literal(value: &DefaultValue, value_type: &str) -> Option<String> {
+  match (value, value_type) {
+    (DefaultValue::Boolean(_), "boolean") | (DefaultValue::String(_), "string") | (_, "any") => {
+      value.to_json()
+    }
+    (value, "number") => value.as_number(),
+    (DefaultValue::Array(_), value_type) if value_type.ends_with("[]") => value.to_json(),
+    (value, "Big") => Some(format!("new Big({:?})", value.as_number()?)),
+    (DefaultValue::String(string), "Date") => Some(format!("new Date({:?})", string)),
+    _ => None,
+  }
+}
+
+// The value a non-nullable field takes when it has no default of its own
+fn zero_value(value_type: &str) -> String {
+  match value_type {
+    "number" => "0".to_string(),
+    "boolean" => "false".to_string(),
+    "any" => "{}".to_string(),
+    "Big" => "new Big(0)".to_string(),
+    "Date" => "new Date(0)".to_string(),
+    "Temporal.Instant" => "Temporal.Instant.fromEpochMilliseconds(0)".to_string(),
+    "Temporal.PlainDate" => "new Temporal.PlainDate(1970, 1, 1)".to_string(),
+    "Temporal.PlainTime" => "new Temporal.PlainTime()".to_string(),
+    "string" => "\"\"".to_string(),
+    array if array.ends_with("[]") => "[]".to_string(),
+    branded => format!("\"\" as {}", branded),
+  }
+}
+
+fn default_field(
+  column: &ColumnData,
+  keys: &[(&ColumnData, &Table)],
+  config: &Config,
+) -> Option<String> {
+  let value_type = value_type(column, &[], config);
+  let literal = column
+    .typed_default(config.adapter)
+    .and_then(|value| default_literal(&value, &value_type));
+  let literal = match (literal, id_type(column, keys, config)) {
+    (Some(literal), Some(id_type)) => Some(format!("{} as {}", literal, id_type)),
+    (literal, _) => literal,
+  };
+  let value = match (literal, column.nullable, config.typescript.nullable) {
+    (Some(literal), _, _) => literal,
+    (None, false, _) => match id_type(column, keys, config) {
+      Some(id_type) => format!("{} as {}", zero_value(&value_type), id_type),
+      None => zero_value(&value_type),
+    },
+    (None, true, TypeScriptNullable::Optional) => return None,
+    (None, true, TypeScriptNullable::Undefined) => "undefined".to_string(),
+    (None, true, _) => "null".to_string(),
+  };
+  match &column.options.default {
+    Some(DefaultValue::Expression(expression)) => Some(format!(
+      "{}: {}, // generated by the database: {}",
+      field_name(&column.name, config),
+      value,
+      expression
+    )),
+    _ => Some(format!("{}: {},", field_name(&column.name, config), value)),
+  }
+}
+
+fn default_factory(
+  table: &Table,
+  name: &str,
+  keys: &[(&ColumnData, &Table)],
+  config: &Config,
+) -> String {
+  if config.typescript.dts {
+    return format!("export declare function default{}(): {};", name, name);
+  }
+  let fields = table
+    .columns
+    .iter()
+    .filter_map(|column| default_field(column, keys, config))
+    .fold(String::new(), |fields, field| {
+      [fields, "    ".to_owned() + &field].join("\n")
     });
+  format!(
+    "export function default{}(): {} {{\n  return {{{}\n  }};\n}}",
+    name, name, fields
+  )
+}
+
+// Decimals arrive as strings, unless they're typed as strings they can be numbers too
+fn is_decimal(column: &ColumnData) -> bool {
+  matches!(
+    column.value_type,
+    RailsColumn::Decimal | RailsColumn::Numeric
+  )
+}
+
+// The values a string column is limited to by an enum or a check constraint
+fn allowed_values(column: &ColumnData) -> &[String] {
+  match &column.options.enum_type {
+    Some(enum_type) if !enum_type.values.is_empty() => &enum_type.values,
+    _ => &column.options.allowed_values,
+  }
+}
+
+// Checks a single value against the column's TypeScript type, or against
+// the JSON it's decoded from
+fn value_check(column: &ColumnData, value_type: &str, value: &str, json: bool) -> String {
+  let allowed_values = allowed_values(column);
+  if value_type == "string" && !allowed_values.is_empty() {
+    let values = allowed_values
+      .iter()
+      .map(|value| format!("{:?}", value))
+      .collect::<Vec<String>>()
+      .join(", ");
+    return format!("([{}] as unknown[]).includes({})", values, value);
+  }
+  match value_type {
+    "number" | "Big" if json && is_decimal(column) => format!(
+      "typeof {} === \"string\" || typeof {} === \"number\"",
+      value, value
+    ),
+    "number" | "string" | "boolean" => format!("typeof {} === {:?}", value, value_type),
+    "any" => format!("{} !== undefined", value),
+    "Big" | "Date" | "Temporal.Instant" | "Temporal.PlainDate" | "Temporal.PlainTime" if !json => {
+      format!("{} instanceof {}", value, value_type)
+    }
+    _ => format!("typeof {} === \"string\"", value),
+  }
+}
+
+fn grouped(check: String) -> String {
+  if check.contains(" || ") || check.contains(" && ") {
+    format!("({})", check)
+  } else {
+    check
+  }
+}
 
-    let name = format_name(&self.name);
-    format!("type {} {{{}\n}}", name, spec)
+// Checks a column's value is present, of the right type and only null when
+// the column is nullable
+fn column_check(column: &ColumnData, value: &str, json: bool, config: &Config) -> String {
+  let value_type = value_type(column, &[], config);
+  let check = match value_type.strip_suffix("[]") {
+    Some(item_type) => format!(
+      "Array.isArray({}) && ({} as unknown[]).every((item) => {})",
+      value,
+      value,
+      value_check(column, item_type, "item", json)
+    ),
+    None => value_check(column, &value_type, value, json),
+  };
+  if !column.nullable {
+    return check;
+  }
+  // Anything goes in a JSON column, so only its presence can be checked
+  if value_type == "any" {
+    return match config.typescript.nullable {
+      TypeScriptNullable::Null => check,
+      _ => "true".to_string(),
+    };
+  }
+  // Missing keys are fine when the type allows them, and always decode to nothing
+  let empty = match (json, config.typescript.nullable) {
+    (_, TypeScriptNullable::Null) => format!("{} === null", value),
+    (false, TypeScriptNullable::Undefined | TypeScriptNullable::Optional) => {
+      format!("{} === undefined", value)
+    }
+    _ => format!("{} == null", value),
+  };
+  format!("{} || {}", empty, grouped(check))
+}
+
+// Turns a single JSON value into the column's TypeScript type
+fn conversion(column: &ColumnData, value_type: &str, value: &str) -> String {
+  match value_type {
+    "number" if is_decimal(column) => format!("Number({})", value),
+    "Big" => format!("new Big({} as string | number)", value),
+    "Date" => format!("new Date({} as string)", value),
+    "Temporal.Instant" | "Temporal.PlainDate" => {
+      format!("{}.from({} as string)", value_type, value)
+    }
+    "Temporal.PlainTime" => format!(
+      "Temporal.Instant.from({} as string).toZonedDateTimeISO(\"UTC\").toPlainTime()",
+      value
+    ),
+    "any" => value.to_string(),
+    value_type => format!("{} as {}", value, value_type),
+  }
+}
+
+fn column_conversion(
+  column: &ColumnData,
+  value: &str,
+  keys: &[(&ColumnData, &Table)],
+  config: &Config,
+) -> String {
+  let value_type = value_type(column, keys, config);
+  let converted = match value_type.strip_suffix("[]") {
+    Some(item_type) => match conversion(column, item_type, "item") {
+      item if item.starts_with("item as ") => format!("{} as {}", value, value_type),
+      item => format!("({} as unknown[]).map((item) => {})", value, item),
+    },
+    None => conversion(column, &value_type, value),
+  };
+  if !column.nullable {
+    return converted;
+  }
+  let empty = match config.typescript.nullable {
+    TypeScriptNullable::Null | TypeScriptNullable::OptionalNull => "null",
+    TypeScriptNullable::Undefined | TypeScriptNullable::Optional => "undefined",
+  };
+  format!("{} == null ? {} : {}", value, empty, grouped(converted))
+}
+
+// A type guard for values of the table's type, and a function that checks
+// the JSON Rails sends and turns it into one, naming every key that's wrong
+fn guards(table: &Table, name: &str, keys: &[(&ColumnData, &Table)], config: &Config) -> String {
+  if config.typescript.dts {
+    return format!(
+      "export declare function is{}(value: unknown): value is {};\nexport declare function decode{}(json: unknown): {};",
+      name, name, name, name
+    );
+  }
+  let field = |column: &ColumnData| format!("record[{:?}]", property_name(&column.name, config));
+  let checks = table
+    .columns
+    .iter()
+    .map(|column| grouped(column_check(column, &field(column), false, config)))
+    .filter(|check| check != "true")
+    .collect::<Vec<String>>();
+  let checks = if checks.is_empty() {
+    "true".to_string()
+  } else {
+    format!("(\n    {}\n  )", checks.join(" &&\n    "))
+  };
+  let json_checks = table.columns.iter().fold(String::new(), |checks, column| {
+    let value = format!("record[{:?}]", column.name);
+    [
+      checks,
+      format!(
+        "    {}: {},",
+        key(column.name.clone()),
+        column_check(column, &value, true, config)
+      ),
+    ]
+    .join("\n")
+  });
+  let fields = table.columns.iter().fold(String::new(), |fields, column| {
+    let value = format!("record[{:?}]", column.name);
+    [
+      fields,
+      format!(
+        "    {}: {},",
+        field_name(&column.name, config),
+        column_conversion(column, &value, keys, config)
+      ),
+    ]
+    .join("\n")
+  });
+  format!(
+    "export function is{name}(value: unknown): value is {name} {{
+  if (typeof value !== \"object\" || value === null) {{
+    return false;
+  }}
+  const record = value as Record<string, unknown>;
+  return {checks};
+}}
+
+export function decode{name}(json: unknown): {name} {{
+  if (typeof json !== \"object\" || json === null) {{
+    throw new TypeError(\"Expected {name} to be an object\");
+  }}
+  const record = json as Record<string, unknown>;
+  const invalid = Object.entries({{{json_checks}
+  }})
+    .filter(([, valid]) => !valid)
+    .map(([key]) => key);
+  if (invalid.length > 0) {{
+    throw new TypeError(`Invalid {name}: ${{invalid.join(\", \")}}`);
+  }}
+  return {{{fields}
+  }};
+}}"
+  )
+}
+
+impl ToTypeScript for NumericType {
+  fn to_typescript(&self, config: &Config) -> String {
+    match (self, config.typescript.decimal) {
+      (NumericType::Decimal, TypeScriptDecimal::String) => "string".to_string(),
+      (NumericType::Decimal, TypeScriptDecimal::Big) => "Big".to_string(),
+      _ => "number".to_string(),
+    }
+  }
+}
+
+fn date_time_type(column: &RailsColumn, date_time: TypeScriptDateTime) -> &'static str {
+  match (date_time, column) {
+    (TypeScriptDateTime::Date, RailsColumn::Datetime | RailsColumn::Date) => "Date",
+    (TypeScriptDateTime::Branded, RailsColumn::Datetime) => "IsoDateTime",
+    (TypeScriptDateTime::Branded, RailsColumn::Date) => "IsoDate",
+    (TypeScriptDateTime::Branded, RailsColumn::Time) => "IsoTime",
+    (TypeScriptDateTime::Temporal, RailsColumn::Datetime) => "Temporal.Instant",
+    (TypeScriptDateTime::Temporal, RailsColumn::Date) => "Temporal.PlainDate",
+    (TypeScriptDateTime::Temporal, RailsColumn::Time) => "Temporal.PlainTime",
+    _ => "string",
   }
 }
 
 impl ToTypeScript for RailsColumn {
-  fn to_typescript(&self) -> String {
+  fn to_typescript(&self, config: &Config) -> String {
+    if let Some(numeric_type) = self.numeric_type(config.adapter) {
+      return numeric_type.to_typescript(config);
+    }
     match self {
-      RailsColumn::PrimaryKey => "number".to_string(),
+      RailsColumn::PrimaryKey
+      | RailsColumn::Integer
+      | RailsColumn::Bigint
+      | RailsColumn::Float
+      | RailsColumn::Decimal
+      | RailsColumn::Numeric => unreachable!("numeric columns are handled above"),
       RailsColumn::String => "string".to_string(),
       RailsColumn::Text => "string".to_string(),
-      RailsColumn::Integer => "number".to_string(),
-      RailsColumn::Bigint => "number".to_string(),
-      RailsColumn::Float => "number".to_string(),
-      RailsColumn::Decimal => "number".to_string(),
-      RailsColumn::Numeric => "number".to_string(),
-      RailsColumn::Datetime => "string".to_string(),
-      RailsColumn::Time => "string".to_string(),
-      RailsColumn::Date => "string".to_string(),
+      RailsColumn::Datetime | RailsColumn::Time | RailsColumn::Date => {
+        date_time_type(self, config.typescript.date_time).to_string()
+      }
       RailsColumn::Binary => "string".to_string(),
-      RailsColumn::Boolean => "bool".to_string(),
+      RailsColumn::Boolean => "boolean".to_string(),
       RailsColumn::JsonB => "any".to_string(),
       RailsColumn::HStore => "any".to_string(),
+      RailsColumn::Uuid => "string".to_string(),
+      RailsColumn::Enum => "string".to_string(),
     }
   }
 }
 
+// The column's type, or the branded id of the table whose key it holds
+fn value_type(column: &ColumnData, keys: &[(&ColumnData, &Table)], config: &Config) -> String {
+  let value_type = match (
+    id_type(column, keys, config),
+    column.numeric_type(config.adapter),
+  ) {
+    (Some(id_type), _) => id_type,
+    (None, Some(numeric_type)) => numeric_type.to_typescript(config),
+    (None, None) => column.value_type.to_typescript(config),
+  };
+  if column.options.array {
+    format!("{}[]", value_type)
+  } else {
+    value_type
+  }
+}
+
 impl ToTypeScript for ColumnData {
-  fn to_typescript(&self) -> String {
-    if self.nullable {
-      format!("{}?: {};", self.name, self.value_type.to_typescript())
-    } else {
-      format!("{}: {};", self.name, self.value_type.to_typescript())
+  fn to_typescript(&self, config: &Config) -> String {
+    column_field(self, &[], config)
+  }
+}
+
+fn column_field(column: &ColumnData, keys: &[(&ColumnData, &Table)], config: &Config) -> String {
+  let value_type = value_type(column, keys, config);
+  match (column.nullable, config.typescript.nullable) {
+    (false, _) => format!("{}: {};", field_name(&column.name, config), value_type),
+    (true, TypeScriptNullable::Null) => {
+      format!(
+        "{}: {} | null;",
+        field_name(&column.name, config),
+        value_type
+      )
+    }
+    (true, TypeScriptNullable::Undefined) => {
+      format!(
+        "{}: {} | undefined;",
+        field_name(&column.name, config),
+        value_type
+      )
+    }
+    (true, TypeScriptNullable::Optional) => {
+      format!("{}?: {};", field_name(&column.name, config), value_type)
+    }
+    (true, TypeScriptNullable::OptionalNull) => {
+      format!(
+        "{}?: {} | null;",
+        field_name(&column.name, config),
+        value_type
+      )
     }
   }
 }
 
 #[cfg(test)]
 pub mod test {
-  use crate::{Database, typescript::ToTypeScript};
+  use crate::{
+    case::Case,
+    config::Config,
+    typescript::{
+      ToTypeScript, TypeScriptDateTime, TypeScriptDecimal, TypeScriptDeclaration,
+      TypeScriptNullable, TypeScriptReadonly,
+    },
+    Database,
+  };
   #[test]
   fn can_convert_a_rails_schema_to_a_string_version_of_a_rust_struct() {
     let schema = "ActiveRecord::Schema.define(version: 20_210_916_202_951) do
@@ -98,25 +927,599 @@ pub mod test {
   end
 end";
     assert_eq!(
-      Database::from(schema).to_typescript(),
-      "type SampleSchema {
-   a?: number;
-   b?: string;
-   c?: string;
-   d?: number;
-   e?: number;
-   f?: number;
-   g?: number;
-   h?: number;
-   i?: string;
-   j?: string;
-   k?: string;
-   l?: string;
-   m?: bool;
-   n?: any;
-   o?: any;
+      Database::from(schema).to_typescript(&Config::default()),
+      "export type SampleSchema = {
+   a: number | null;
+   b: string | null;
+   c: string | null;
+   d: number | null;
+   e: number | null;
+   f: number | null;
+   g: string | null;
+   h: string | null;
+   i: string | null;
+   j: string | null;
+   k: string | null;
+   l: string | null;
+   m: boolean | null;
+   n: any | null;
+   o: any | null;
    created_at: string;
    updated_at: string;
+};"
+    )
+  }
+
+  #[test]
+  fn decimals_can_be_represented_with_big() {
+    let schema = "create_table \"accounts\", id: false, force: :cascade do |t|
+    t.decimal \"balance\", precision: 10, scale: 2, null: false
+    t.decimal \"cents\", precision: 9, scale: 0, null: false
+  end";
+    let mut config = Config::default();
+    config.typescript.decimal = TypeScriptDecimal::Big;
+    assert_eq!(
+      Database::from(schema).to_typescript(&config),
+      "import Big from \"big.js\";
+
+export type Account = {
+   balance: Big;
+   cents: number;
+};"
+    )
+  }
+
+  #[test]
+  fn dates_and_times_can_be_branded_strings_or_temporal_types() {
+    let schema = "create_table \"events\", id: false, force: :cascade do |t|
+    t.datetime \"starts_at\", null: false
+    t.date \"day\", null: false
+  end";
+    let mut config = Config::default();
+    config.typescript.date_time = TypeScriptDateTime::Branded;
+    assert_eq!(
+      Database::from(schema).to_typescript(&config),
+      "export type IsoDateTime = string & { readonly __brand: \"IsoDateTime\" };
+export type IsoDate = string & { readonly __brand: \"IsoDate\" };
+
+export type Event = {
+   starts_at: IsoDateTime;
+   day: IsoDate;
+};"
+    );
+
+    config.typescript.date_time = TypeScriptDateTime::Temporal;
+    assert_eq!(
+      Database::from(schema).to_typescript(&config),
+      "import { Temporal } from \"@js-temporal/polyfill\";
+
+export type Event = {
+   starts_at: Temporal.Instant;
+   day: Temporal.PlainDate;
+};"
+    )
+  }
+
+  #[test]
+  fn nullable_columns_can_be_optional_keys() {
+    let schema = "create_table \"users\", id: false, force: :cascade do |t|
+    t.string \"name\", null: false
+    t.string \"nickname\"
+  end";
+    let mut config = Config::default();
+    config.typescript.nullable = TypeScriptNullable::Optional;
+    assert_eq!(
+      Database::from(schema).to_typescript(&config),
+      "export type User = {
+   name: string;
+   nickname?: string;
+};"
+    );
+
+    config.typescript.nullable = TypeScriptNullable::OptionalNull;
+    assert_eq!(
+      Database::from(schema).to_typescript(&config),
+      "export type User = {
+   name: string;
+   nickname?: string | null;
+};"
+    )
+  }
+
+  #[test]
+  fn column_defaults_become_a_factory_function() {
+    let schema = "create_table \"posts\", id: false, force: :cascade do |t|
+    t.string \"title\", null: false
+    t.string \"subtitle\"
+    t.string \"status\", default: \"draft\", null: false
+    t.integer \"views\", default: 0
+    t.jsonb \"settings\", default: {}, null: false
+    t.datetime \"published_at\", default: -> { \"now()\" }, null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_typescript(&Config::default()),
+      "export type Post = {
+   title: string;
+   subtitle: string | null;
+   status: string;
+   views: number | null;
+   settings: any;
+   published_at: string;
+};
+
+export function defaultPost(): Post {
+  return {
+    title: \"\",
+    subtitle: null,
+    status: \"draft\",
+    views: 0,
+    settings: {},
+    published_at: \"\", // generated by the database: now()
+  };
+}"
+    )
+  }
+
+  #[test]
+  fn tables_can_have_insert_and_patch_variants() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+    t.string \"name\", null: false
+    t.string \"role\", default: \"member\", null: false
+    t.string \"nickname\"
+  end";
+    let mut config = Config::default();
+    config.typescript.defaults = false;
+    config.typescript.variants.enabled = true;
+    assert_eq!(
+      Database::from(schema).to_typescript(&config),
+      "export type User = {
+   id: number;
+   name: string;
+   role: string;
+   nickname: string | null;
+};
+
+export type NewUser = {
+   name: string;
+   role?: string;
+   nickname?: string | null;
+};
+
+export type UserPatch = {
+   name?: string;
+   role?: string;
+   nickname?: string | null;
+};"
+    )
+  }
+
+  #[test]
+  fn foreign_keys_can_become_optional_association_fields() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+  end
+  create_table \"posts\", force: :cascade do |t|
+    t.bigint \"user_id\", null: false
+  end
+  add_foreign_key \"posts\", \"users\"";
+    let mut config = Config::default();
+    config.typescript.associations = true;
+    assert_eq!(
+      Database::from(schema).to_typescript(&config),
+      "export type User = {
+   id: number;
+   posts?: Post[];
+};
+
+export type Post = {
+   id: number;
+   user_id: number;
+   user?: User;
+};"
+    )
+  }
+
+  #[test]
+  fn reserved_words_and_invalid_identifiers_are_quoted() {
+    let schema = "create_table \"2fa_codes\", id: false, force: :cascade do |t|
+    t.string \"type\", null: false
+    t.string \"default\", null: false
+    t.integer \"range\", null: false
+    t.string \"123abc\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_typescript(&Config::default()),
+      "export type _2faCode = {
+   type: string;
+   \"default\": string;
+   range: number;
+   \"123abc\": string;
+};"
+    )
+  }
+
+  #[test]
+  fn keys_are_the_column_names_as_they_are_by_default() {
+    let schema = "create_table \"imports\", id: false, force: :cascade do |t|
+    t.string \"legacyCode\", null: false
+    t.string \"first-name\", null: false
+    t.string \"ID\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_typescript(&Config::default()),
+      "export type Import = {
+   legacyCode: string;
+   \"first-name\": string;
+   ID: string;
+};"
+    )
+  }
+
+  #[test]
+  fn guards_check_the_keys_the_type_has() {
+    let schema = "create_table \"imports\", id: false, force: :cascade do |t|
+    t.string \"legacyCode\", null: false
+  end";
+    let mut config = Config::default();
+    config.typescript.guards = true;
+    assert_eq!(
+      Database::from(schema).to_typescript(&config),
+      "export type Import = {
+   legacyCode: string;
+};
+
+export function isImport(value: unknown): value is Import {
+  if (typeof value !== \"object\" || value === null) {
+    return false;
+  }
+  const record = value as Record<string, unknown>;
+  return (
+    typeof record[\"legacyCode\"] === \"string\"
+  );
+}
+
+export function decodeImport(json: unknown): Import {
+  if (typeof json !== \"object\" || json === null) {
+    throw new TypeError(\"Expected Import to be an object\");
+  }
+  const record = json as Record<string, unknown>;
+  const invalid = Object.entries({
+    legacyCode: typeof record[\"legacyCode\"] === \"string\",
+  })
+    .filter(([, valid]) => !valid)
+    .map(([key]) => key);
+  if (invalid.length > 0) {
+    throw new TypeError(`Invalid Import: ${invalid.join(\", \")}`);
+  }
+  return {
+    legacyCode: record[\"legacyCode\"] as string,
+  };
+}"
+    )
+  }
+
+  #[test]
+  fn keys_that_are_not_renamed_need_no_map() {
+    let schema = "create_table \"users\", id: false, force: :cascade do |t|
+    t.string \"name\", null: false
+  end";
+    let mut config = Config::default();
+    config.typescript.field_case = Case::Camel;
+    assert_eq!(
+      Database::from(schema).to_typescript(&config),
+      "export type User = {
+   name: string;
+};"
+    )
+  }
+
+  #[test]
+  fn camel_case_keys_come_with_a_map_back_to_rails_keys() {
+    let schema = "create_table \"users\", id: false, force: :cascade do |t|
+    t.string \"first_name\", null: false
+    t.datetime \"created_at\", null: false
+  end";
+    let mut config = Config::default();
+    config.typescript.field_case = Case::Camel;
+    let typescript = Database::from(schema).to_typescript(&config);
+    assert!(typescript.starts_with("export function fromJson<T>("));
+    assert!(typescript.ends_with(
+      "export type User = {
+   firstName: string;
+   createdAt: string;
+};
+
+export const UserKeys: Record<keyof User, string> = {
+  firstName: \"first_name\",
+  createdAt: \"created_at\",
+};"
+    ))
+  }
+
+  #[test]
+  fn tables_can_be_interfaces_with_readonly_keys_and_timestamps() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+    t.string \"name\", null: false
+    t.boolean \"admin\", default: false, null: false
+    t.datetime \"created_at\", null: false
+    t.datetime \"updated_at\", null: false
+  end";
+    let mut config = Config::default();
+    config.typescript.declaration = TypeScriptDeclaration::Interface;
+    config.typescript.readonly = vec![
+      TypeScriptReadonly::PrimaryKeys,
+      TypeScriptReadonly::Timestamps,
+    ];
+    config.typescript.variants.enabled = true;
+    assert_eq!(
+      Database::from(schema).to_typescript(&config),
+      "export interface User {
+   readonly id: number;
+   name: string;
+   admin: boolean;
+   readonly created_at: string;
+   readonly updated_at: string;
+}
+
+export function defaultUser(): User {
+  return {
+    id: 0,
+    name: \"\",
+    admin: false,
+    created_at: \"\",
+    updated_at: \"\",
+  };
+}
+
+export interface NewUser {
+   name: string;
+   admin?: boolean;
+   created_at?: string;
+   updated_at?: string;
+}
+
+export interface UserPatch {
+   name?: string;
+   admin?: boolean;
+   created_at?: string;
+   updated_at?: string;
+}"
+    );
+  }
+
+  #[test]
+  fn declaration_files_leave_out_function_bodies_and_values() {
+    let schema = "create_table \"users\", id: false, force: :cascade do |t|
+    t.string \"first_name\", default: \"\", null: false
+  end";
+    let mut config = Config::default();
+    config.typescript.dts = true;
+    config.typescript.field_case = Case::Camel;
+    assert_eq!(Database::from(schema).to_typescript(&config),
+      "export declare function fromJson<T>(keys: Record<keyof T, string>, json: Record<string, unknown>): T;
+export declare function toJson<T extends object>(keys: Record<keyof T, string>, value: T): Record<string, unknown>;
+
+export type User = {
+   firstName: string;
+};
+
+export declare const UserKeys: Record<keyof User, string>;
+
+export declare function defaultUser(): User;"
+    );
+  }
+
+  #[test]
+  fn guards_check_the_json_rails_sends() {
+    let schema = "create_enum \"mood\", [\"happy\", \"sad\"]
+  create_table \"users\", force: :cascade do |t|
+    t.string \"role\", null: false
+    t.enum \"mood\", enum_type: \"mood\"
+    t.string \"tags\", array: true, null: false
+    t.decimal \"balance\", precision: 10, scale: 2
+    t.jsonb \"settings\"
+    t.datetime \"created_at\", null: false
+    t.check_constraint \"role IN ('member', 'admin')\", name: \"role_check\"
+  end";
+    let mut config = Config::default();
+    config.typescript.guards = true;
+    config.typescript.defaults = false;
+    config.typescript.date_time = TypeScriptDateTime::Date;
+    config.typescript.decimal = TypeScriptDecimal::Big;
+    config.typescript.nullable = TypeScriptNullable::Optional;
+    assert_eq!(Database::from(schema).to_typescript(&config),
+      "import Big from \"big.js\";
+
+export type User = {
+   id: number;
+   role: string;
+   mood?: string;
+   tags: string[];
+   balance?: Big;
+   settings?: any;
+   created_at: Date;
+};
+
+export function isUser(value: unknown): value is User {
+  if (typeof value !== \"object\" || value === null) {
+    return false;
+  }
+  const record = value as Record<string, unknown>;
+  return (
+    typeof record[\"id\"] === \"number\" &&
+    ([\"member\", \"admin\"] as unknown[]).includes(record[\"role\"]) &&
+    (record[\"mood\"] === undefined || ([\"happy\", \"sad\"] as unknown[]).includes(record[\"mood\"])) &&
+    (Array.isArray(record[\"tags\"]) && (record[\"tags\"] as unknown[]).every((item) => typeof item === \"string\")) &&
+    (record[\"balance\"] === undefined || record[\"balance\"] instanceof Big) &&
+    record[\"created_at\"] instanceof Date
+  );
+}
+
+export function decodeUser(json: unknown): User {
+  if (typeof json !== \"object\" || json === null) {
+    throw new TypeError(\"Expected User to be an object\");
+  }
+  const record = json as Record<string, unknown>;
+  const invalid = Object.entries({
+    id: typeof record[\"id\"] === \"number\",
+    role: ([\"member\", \"admin\"] as unknown[]).includes(record[\"role\"]),
+    mood: record[\"mood\"] == null || ([\"happy\", \"sad\"] as unknown[]).includes(record[\"mood\"]),
+    tags: Array.isArray(record[\"tags\"]) && (record[\"tags\"] as unknown[]).every((item) => typeof item === \"string\"),
+    balance: record[\"balance\"] == null || (typeof record[\"balance\"] === \"string\" || typeof record[\"balance\"] === \"number\"),
+    settings: true,
+    created_at: typeof record[\"created_at\"] === \"string\",
+  })
+    .filter(([, valid]) => !valid)
+    .map(([key]) => key);
+  if (invalid.length > 0) {
+    throw new TypeError(`Invalid User: ${invalid.join(\", \")}`);
+  }
+  return {
+    id: record[\"id\"] as number,
+    role: record[\"role\"] as string,
+    mood: record[\"mood\"] == null ? undefined : record[\"mood\"] as string,
+    tags: record[\"tags\"] as string[],
+    balance: record[\"balance\"] == null ? undefined : new Big(record[\"balance\"] as string | number),
+    settings: record[\"settings\"] == null ? undefined : record[\"settings\"],
+    created_at: new Date(record[\"created_at\"] as string),
+  };
+}"
+    );
+  }
+
+  #[test]
+  fn keys_can_be_branded_with_their_table() {
+    let schema = "create_table \"users\", force: :cascade do |t|
+  end
+  create_table \"posts\", id: :uuid, force: :cascade do |t|
+    t.bigint \"author_id\", null: false
+    t.bigint \"editor_id\"
+  end
+  add_foreign_key \"posts\", \"users\", column: \"author_id\"
+  add_foreign_key \"posts\", \"users\", column: \"editor_id\"";
+    let mut config = Config::default();
+    config.typescript.id_types = true;
+    config.typescript.variants.enabled = true;
+    assert_eq!(
+      Database::from(schema).to_typescript(&config),
+      "export type UserId = number & { readonly __brand: \"UserId\" };
+
+export type User = {
+   id: UserId;
+};
+
+export type NewUser = {
+};
+
+export type UserPatch = {
+};
+
+export type PostId = string & { readonly __brand: \"PostId\" };
+
+export type Post = {
+   id: PostId;
+   author_id: UserId;
+   editor_id: UserId | null;
+};
+
+export type NewPost = {
+   id: PostId;
+   author_id: UserId;
+   editor_id?: UserId | null;
+};
+
+export type PostPatch = {
+   id?: PostId;
+   author_id?: UserId;
+   editor_id?: UserId | null;
+};"
+    );
+  }
+
+  #[test]
+  fn the_output_is_valid_typescript() {
+    let schema = "create_enum \"status\", [\"draft\", \"published\"]
+  create_table \"2fa_codes\", force: :cascade do |t|
+    t.enum \"status\", enum_type: \"status\", default: \"draft\", null: false
+    t.string \"labels\", array: true, default: [\"new\"], null: false
+    t.hstore \"settings\"
+    t.string \"default\"
+    t.string \"123abc\"
+    t.decimal \"balance\", precision: 10, scale: 2, default: \"0.0\", null: false
+    t.float \"score\", null: false
+    t.time \"at\", null: false
+    t.date \"on\", null: false
+    t.boolean \"active\", default: true, null: false
+    t.jsonb \"meta\", default: {}, null: false
+    t.bigint \"user_id\"
+    t.datetime \"created_at\", default: -> { \"now()\" }, null: false
+  end
+  create_table \"users\", force: :cascade do |t|
+  end
+  add_foreign_key \"2fa_codes\", \"users\"";
+    let mut config = Config::default();
+    config.typescript.variants.enabled = true;
+    config.typescript.associations = true;
+    config.typescript.guards = true;
+    config.typescript.id_types = true;
+    config.typescript.readonly = vec![
+      TypeScriptReadonly::PrimaryKeys,
+      TypeScriptReadonly::Timestamps,
+    ];
+    for declaration in [
+      TypeScriptDeclaration::Type,
+      TypeScriptDeclaration::Interface,
+    ] {
+      for dts in [false, true] {
+        for date_time in [
+          TypeScriptDateTime::String,
+          TypeScriptDateTime::Date,
+          TypeScriptDateTime::Branded,
+          TypeScriptDateTime::Temporal,
+        ] {
+          for field_case in [Case::Snake, Case::Camel] {
+            config.typescript.declaration = declaration;
+            config.typescript.dts = dts;
+            config.typescript.date_time = date_time;
+            config.typescript.decimal = TypeScriptDecimal::Big;
+            config.typescript.field_case = field_case;
+            let typescript = Database::from(schema).to_typescript(&config);
+            let source_type = if dts {
+              oxc_span::SourceType::d_ts()
+            } else {
+              oxc_span::SourceType::ts()
+            };
+            let allocator = oxc_allocator::Allocator::default();
+            let parsed = oxc_parser::Parser::new(&allocator, &typescript, source_type).parse();
+            if let Some(error) = parsed.errors.first() {
+              panic!("{}\n\n{}", error, typescript);
+            }
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn numeric_defaults_take_the_column_type() {
+    let schema = "create_table \"accounts\", id: false, force: :cascade do |t|
+    t.decimal \"balance\", precision: 10, scale: 2, default: \"0.0\", null: false
+    t.float \"ratio\", default: 0, null: false
+    t.decimal \"amount\", precision: 9, scale: 0, default: \"5\", null: false
+  end";
+    assert_eq!(
+      Database::from(schema).to_typescript(&Config::default()),
+      "export type Account = {
+   balance: string;
+   ratio: number;
+   amount: number;
+};
+
+export function defaultAccount(): Account {
+  return {
+    balance: \"0.0\",
+    ratio: 0,
+    amount: 5,
+  };
 }"
     )
   }
@@ -0,0 +1,288 @@
+use super::config::Config;
+use super::typescript::{key, TypeScriptDateTime, TypeScriptDecimal, TypeScriptNullable};
+use super::ColumnData;
+use super::Database;
+use super::NumericType;
+use super::RailsColumn;
+use super::Table;
+
+pub trait ToZod {
+  fn to_zod(&self, config: &Config) -> String;
+}
+
+// Zod schemas validate the JSON Rails sends, so they're keyed by column
+// name whatever the TypeScript field case is
+impl ToZod for Database {
+  fn to_zod(&self, config: &Config) -> String {
+    let schemas = self
+      .0
+      .iter()
+      .map(|table| table.to_zod(config))
+      .collect::<Vec<String>>()
+      .join("\n\n");
+    let mut imports = vec!["import { z } from \"zod\";"];
+    if schemas.contains("new Big(") {
+      imports.push("import Big from \"big.js\";");
+    }
+    if schemas.contains("Temporal.") {
+      imports.push("import { Temporal } from \"@js-temporal/polyfill\";");
+    }
+    [imports.join("\n"), schemas]
+      .into_iter()
+      .filter(|section| !section.is_empty())
+      .collect::<Vec<String>>()
+      .join("\n\n")
+  }
+}
+
+// A schema named after the table's type, along with the type it parses into
+impl ToZod for Table {
+  fn to_zod(&self, config: &Config) -> String {
+    let name = self.type_name(config);
+    let fields = self.columns.iter().fold(String::new(), |fields, column| {
+      [
+        fields,
+        format!("  {}: {},", key(column.name.clone()), column.to_zod(config)),
+      ]
+      .join("\n")
+    });
+    format!(
+      "export const {} = z.object({{{}\n}});\nexport type {} = z.infer<typeof {}>;",
+      name, fields, name, name
+    )
+  }
+}
+
+impl ToZod for NumericType {
+  fn to_zod(&self, config: &Config) -> String {
+    match (self, config.typescript.decimal) {
+      (NumericType::SmallInt | NumericType::Int | NumericType::BigInt, _) => {
+        "z.number().int()".to_string()
+      }
+      (NumericType::Float, _) => "z.number()".to_string(),
+      (NumericType::Decimal, TypeScriptDecimal::String) => "z.string()".to_string(),
+      // Rails sends decimals as strings, which have to be turned into numbers
+      (NumericType::Decimal, TypeScriptDecimal::Number) => "z.coerce.number()".to_string(),
+      (NumericType::Decimal, TypeScriptDecimal::Big) => {
+        "z.coerce.string().transform((value) => new Big(value))".to_string()
+      }
+    }
+  }
+}
+
+// Rails sends times of day as a timestamp on 2000-01-01 in UTC, so they
+// can't be checked with `time()` or read with `PlainTime.from`
+fn date_time_schema(column: &RailsColumn, date_time: TypeScriptDateTime) -> &'static str {
+  match (date_time, column) {
+    (TypeScriptDateTime::Date, RailsColumn::Datetime | RailsColumn::Date) => "z.coerce.date()",
+    (TypeScriptDateTime::Branded, RailsColumn::Datetime) => {
+      "z.string().datetime({ offset: true }).brand<\"IsoDateTime\">()"
+    }
+    (TypeScriptDateTime::Branded, RailsColumn::Date) => "z.string().date().brand<\"IsoDate\">()",
+    (TypeScriptDateTime::Branded, RailsColumn::Time) => "z.string().brand<\"IsoTime\">()",
+    (TypeScriptDateTime::Temporal, RailsColumn::Datetime) => {
+      "z.string().transform((value) => Temporal.Instant.from(value))"
+    }
+    (TypeScriptDateTime::Temporal, RailsColumn::Date) => {
+      "z.string().transform((value) => Temporal.PlainDate.from(value))"
+    }
+    (TypeScriptDateTime::Temporal, RailsColumn::Time) => {
+      "z.string().transform((value) => Temporal.Instant.from(value).toZonedDateTimeISO(\"UTC\").toPlainTime())"
+    }
+    (_, RailsColumn::Datetime) => "z.string().datetime({ offset: true })",
+    (_, RailsColumn::Date) => "z.string().date()",
+    _ => "z.string()",
+  }
+}
+
+impl ToZod for RailsColumn {
+  fn to_zod(&self, config: &Config) -> String {
+    if let Some(numeric_type) = self.numeric_type(config.adapter) {
+      return numeric_type.to_zod(config);
+    }
+    match self {
+      RailsColumn::PrimaryKey
+      | RailsColumn::Integer
+      | RailsColumn::Bigint
+      | RailsColumn::Float
+      | RailsColumn::Decimal
+      | RailsColumn::Numeric => unreachable!("numeric columns are handled above"),
+      RailsColumn::String | RailsColumn::Text | RailsColumn::Binary | RailsColumn::Enum => {
+        "z.string()".to_string()
+      }
+      RailsColumn::Datetime | RailsColumn::Time | RailsColumn::Date => {
+        date_time_schema(self, config.typescript.date_time).to_string()
+      }
+      RailsColumn::Boolean => "z.boolean()".to_string(),
+      RailsColumn::JsonB | RailsColumn::HStore => "z.any()".to_string(),
+      RailsColumn::Uuid => "z.string().uuid()".to_string(),
+    }
+  }
+}
+
+fn enum_schema(values: &[String]) -> String {
+  let values = values
+    .iter()
+    .map(|value| format!("{:?}", value))
+    .collect::<Vec<String>>()
+    .join(", ");
+  format!("z.enum([{}])", values)
+}
+
+impl ToZod for ColumnData {
+  fn to_zod(&self, config: &Config) -> String {
+    let options = &self.options;
+    let schema = match (
+      &self.value_type,
+      &options.enum_type,
+      self.numeric_type(config.adapter),
+    ) {
+      (_, Some(enum_type), _) if !enum_type.values.is_empty() => enum_schema(&enum_type.values),
+      (RailsColumn::String | RailsColumn::Text, _, _) if !options.allowed_values.is_empty() => {
+        enum_schema(&options.allowed_values)
+      }
+      (RailsColumn::String | RailsColumn::Text, _, _) => match options.limit {
+        Some(limit) => format!("z.string().max({})", limit),
+        None => "z.string()".to_string(),
+      },
+      // A decimal without a scale is typed as a number, but still sent as a string
+      (
+        RailsColumn::Decimal | RailsColumn::Numeric,
+        _,
+        Some(NumericType::SmallInt | NumericType::Int | NumericType::BigInt),
+      ) => "z.coerce.number().int()".to_string(),
+      (_, _, Some(numeric_type)) => numeric_type.to_zod(config),
+      (value_type, _, None) => value_type.to_zod(config),
+    };
+    let schema = if options.array {
+      format!("z.array({})", schema)
+    } else {
+      schema
+    };
+    match (self.nullable, config.typescript.nullable) {
+      (false, _) => schema,
+      (true, TypeScriptNullable::Null) => format!("{}.nullable()", schema),
+      (true, TypeScriptNullable::Undefined | TypeScriptNullable::Optional) => {
+        format!("{}.optional()", schema)
+      }
+      (true, TypeScriptNullable::OptionalNull) => format!("{}.nullish()", schema),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    config::Config,
+    typescript::{TypeScriptDateTime, TypeScriptDecimal, TypeScriptNullable},
+    zod::ToZod,
+    Database,
+  };
+
+  #[test]
+  fn tables_become_object_schemas_with_an_inferred_type() {
+    let schema = "create_enum \"mood\", [\"happy\", \"sad\"]
+  create_table \"users\", id: :uuid, force: :cascade do |t|
+    t.string \"name\", limit: 100, null: false
+    t.string \"role\", null: false
+    t.enum \"mood\", enum_type: \"mood\"
+    t.string \"tags\", array: true, null: false
+    t.integer \"logins\", null: false
+    t.decimal \"balance\", precision: 10, scale: 2
+    t.decimal \"cents\", precision: 9, scale: 0
+    t.boolean \"admin\", default: false, null: false
+    t.jsonb \"settings\"
+    t.date \"born_on\"
+    t.datetime \"created_at\", null: false
+    t.check_constraint \"role IN ('member', 'admin')\", name: \"role_check\"
+  end";
+    assert_eq!(
+      Database::from(schema).to_zod(&Config::default()),
+      "import { z } from \"zod\";
+
+export const User = z.object({
+  id: z.string().uuid(),
+  name: z.string().max(100),
+  role: z.enum([\"member\", \"admin\"]),
+  mood: z.enum([\"happy\", \"sad\"]).nullable(),
+  tags: z.array(z.string()),
+  logins: z.number().int(),
+  balance: z.string().nullable(),
+  cents: z.coerce.number().int().nullable(),
+  admin: z.boolean(),
+  settings: z.any().nullable(),
+  born_on: z.string().date().nullable(),
+  created_at: z.string().datetime({ offset: true }),
+});
+export type User = z.infer<typeof User>;"
+    );
+  }
+
+  #[test]
+  fn dates_and_nullable_columns_follow_the_typescript_settings() {
+    let schema = "create_table \"events\", id: false, force: :cascade do |t|
+    t.datetime \"starts_at\", null: false
+    t.date \"day\"
+    t.time \"at\"
+    t.decimal \"price\", precision: 8, scale: 2
+  end";
+    let mut config = Config::default();
+    config.typescript.date_time = TypeScriptDateTime::Date;
+    config.typescript.nullable = TypeScriptNullable::Optional;
+    config.typescript.decimal = TypeScriptDecimal::Big;
+    assert_eq!(
+      Database::from(schema).to_zod(&config),
+      "import { z } from \"zod\";
+import Big from \"big.js\";
+
+export const Event = z.object({
+  starts_at: z.coerce.date(),
+  day: z.coerce.date().optional(),
+  at: z.string().optional(),
+  price: z.coerce.string().transform((value) => new Big(value)).optional(),
+});
+export type Event = z.infer<typeof Event>;"
+    );
+  }
+
+  #[test]
+  fn the_output_is_valid_typescript() {
+    let schema = "create_enum \"status\", [\"draft\", \"published\"]
+  create_table \"2fa_codes\", force: :cascade do |t|
+    t.enum \"status\", enum_type: \"status\", null: false
+    t.string \"labels\", array: true
+    t.hstore \"settings\"
+    t.string \"default\", limit: 10
+    t.string \"123abc\"
+    t.decimal \"balance\", precision: 10, scale: 2
+    t.float \"score\", null: false
+    t.time \"at\"
+    t.date \"on\"
+    t.datetime \"created_at\", null: false
+  end";
+    let mut config = Config::default();
+    for date_time in [
+      TypeScriptDateTime::String,
+      TypeScriptDateTime::Date,
+      TypeScriptDateTime::Branded,
+      TypeScriptDateTime::Temporal,
+    ] {
+      for nullable in [
+        TypeScriptNullable::Null,
+        TypeScriptNullable::Undefined,
+        TypeScriptNullable::Optional,
+        TypeScriptNullable::OptionalNull,
+      ] {
+        config.typescript.date_time = date_time;
+        config.typescript.nullable = nullable;
+        config.typescript.decimal = TypeScriptDecimal::Big;
+        let zod = Database::from(schema).to_zod(&config);
+        let allocator = oxc_allocator::Allocator::default();
+        let parsed = oxc_parser::Parser::new(&allocator, &zod, oxc_span::SourceType::ts()).parse();
+        if let Some(error) = parsed.errors.first() {
+          panic!("{}\n\n{}", error, zod);
+        }
+      }
+    }
+  }
+}